@@ -0,0 +1,170 @@
+#!/usr/bin/env rust
+
+//! Keep archives of several accounts apart when converted in one run
+//!
+//! Each account is converted on its own, with its own `Context`, so self-mentions, replies, and
+//! threads only ever resolve to posts of that same account.  Posts are written within a
+//! subdirectory of `--output-directory` named after account handle, and `{user_name}` within
+//! other options is replaced by that handle.
+
+use chrono::{DateTime, Utc};
+use std::{io, path};
+use twitter_archive::structs::manifest::UserInfo;
+
+use crate::arguments::Args;
+use crate::media;
+use crate::output;
+use crate::post_build;
+
+/// Text replaced by account handle within options of multi-account runs
+const USER_NAME: &str = "{user_name}";
+
+/// What `--accounts-index` lists about one account
+pub struct Account {
+	/// Account that requested archive, from newest manifest
+	pub user_info: UserInfo,
+
+	/// When newest archive of account was generated
+	pub generation_date: Option<DateTime<Utc>>,
+
+	/// Directory that posts of account were written within
+	pub directory: path::PathBuf,
+
+	/// Number of archives merged for account
+	pub exports: usize,
+}
+
+/// Copy of `args` for account with `user_name`, writing posts within its own subdirectory of
+/// `output_directory_path`
+pub fn args(user_name: &str, output_directory_path: &path::Path, args: &Args) -> Args {
+	let replace = |value: &String| value.replace(USER_NAME, user_name);
+	let replace_option = |value: &Option<String>| value.as_ref().map(replace);
+
+	Args {
+		output_directory: Some(output_directory_path.join(user_name).display().to_string()),
+		post_author: replace_option(&args.post_author).or(Some(user_name.to_string())),
+		post_twitter_key: match args.post_twitter_key.contains(USER_NAME) {
+			true => replace(&args.post_twitter_key),
+			false => format!("{}_{user_name}", args.post_twitter_key),
+		},
+		post_permalink: replace_option(&args.post_permalink),
+		deleted_output_directory: replace_option(&args.deleted_output_directory),
+		likes_output_directory: Some(match &args.likes_output_directory {
+			Some(directory) => replace(directory),
+			None => format!(
+				"{}/{user_name}",
+				output::sibling_directory(output_directory_path, "likes")
+			),
+		}),
+		direct_messages_directory: replace_option(&args.direct_messages_directory),
+		profile_page: replace_option(&args.profile_page),
+		profile_url: replace_option(&args.profile_url),
+		graph_directory: replace_option(&args.graph_directory),
+		dump_directory: replace_option(&args.dump_directory),
+		..args.clone()
+	}
+}
+
+/// Warn about export paths every account would write to, as the first account to write a file
+/// wins and later ones are skipped
+pub fn warn_shared_paths(args: &Args) {
+	let options = [
+		("--deleted-output-directory", &args.deleted_output_directory),
+		(
+			"--direct-messages-directory",
+			&args.direct_messages_directory,
+		),
+		("--profile-page", &args.profile_page),
+		("--graph-directory", &args.graph_directory),
+		("--dump-directory", &args.dump_directory),
+	];
+
+	for (name, value) in options {
+		if let Some(value) = value.as_ref().filter(|value| !value.contains(USER_NAME)) {
+			eprintln!(
+				"accounts::warn_shared_paths -> {name} is shared by every account, add {USER_NAME} to keep them apart -> {value}"
+			);
+		}
+	}
+}
+
+/// Write `--accounts-index`, if defined, listing every account and where its posts were written
+pub fn write_index(accounts: &[Account], args: &Args) -> io::Result<()> {
+	let Some(accounts_index) = &args.accounts_index else {
+		return Ok(());
+	};
+
+	let accounts_index_path = path::Path::new(accounts_index);
+	let accounts_index_directory_path = accounts_index_path
+		.parent()
+		.unwrap_or(path::Path::new(""))
+		.to_path_buf();
+	output::create_directory(&accounts_index_directory_path, args)?;
+
+	output::write_markdown(
+		accounts_index_path,
+		&index(accounts, &accounts_index_directory_path, args),
+		args,
+	)
+}
+
+/// Build MarkDown, with FrontMatter, listing every account
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ---
+/// layout: accounts
+/// title: "Accounts"
+/// accounts:
+///   - user_name: S0_And_S0
+///     display_name: "S0AndS0"
+///     account_id: "111111111"
+///     account: https://twitter.com/S0_And_S0
+///     generation_date: "2023-08-31 01:02:03 +0000"
+///     exports: 1
+///     directory: _tweets/S0_And_S0
+/// ---
+///
+/// - [S0AndS0 (@S0_And_S0)](_tweets/S0_And_S0), archive of 2023-08-31
+/// ```
+fn index(accounts: &[Account], index_directory_path: &path::Path, args: &Args) -> String {
+	let mut lines: Vec<String> = vec![];
+	let mut body: Vec<String> = vec![];
+
+	lines.push(format!("layout: {}", args.accounts_layout));
+	lines.push("title: \"Accounts\"".into());
+	lines.push("accounts:".into());
+
+	for account in accounts {
+		let user_info = &account.user_info;
+		let directory = media::relative_path(index_directory_path, &account.directory);
+
+		lines.push(format!("  - user_name: {}", user_info.user_name));
+		lines.push(format!("    display_name: {:?}", user_info.display_name));
+		lines.push(format!("    account_id: {:?}", user_info.account_id));
+		lines.push(format!(
+			"    account: {}",
+			post_build::twitter_url_account(&user_info.user_name, args)
+		));
+		if let Some(generation_date) = account.generation_date {
+			lines.push(format!(
+				"    generation_date: {:?}",
+				generation_date.format(&args.post_date_format).to_string()
+			));
+		}
+		lines.push(format!("    exports: {}", account.exports));
+		lines.push(format!("    directory: {directory}"));
+
+		let mut item = format!(
+			"- [{} (@{})]({directory})",
+			user_info.display_name, user_info.user_name
+		);
+		if let Some(generation_date) = account.generation_date {
+			item.push_str(&format!(", archive of {}", generation_date.format("%F")));
+		}
+		body.push(item);
+	}
+
+	format!("---\n{}\n---\n\n{}\n", lines.join("\n"), body.join("\n"))
+}
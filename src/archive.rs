@@ -0,0 +1,526 @@
+#!/usr/bin/env rust
+
+//! Uniform access to files within a packed, eg. zip, tar, or 7z, or unpacked, Twitter archive
+//!
+//! Large archives are split across several zip files, eg. `twitter-<date>-<hash>-part1.zip` and
+//! `twitter-<date>-<hash>-part2.zip`, so packed file names are indexed across every part.  Each
+//! part may be of any format that implements `Reader`.
+
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Read;
+use std::{fs, io, path};
+use twitter_archive::structs::manifest;
+use zip::read::ZipArchive;
+
+use crate::arguments::Args;
+use crate::sevenz::SevenZArchive;
+use crate::tar::{self, TarArchive};
+
+/// Packed archive file that files may be looked up, and streamed, by name
+pub trait Reader {
+	/// Every file name held, eg. `data/tweets_media/123-abc.jpg`
+	fn file_names(&self) -> Vec<String>;
+
+	/// Uncompressed size, in bytes, of `file_name`, or `None` when it does not exist
+	fn file_size(&mut self, file_name: &str) -> Option<u64>;
+
+	/// Stream uncompressed content of `file_name`
+	fn read<'a>(&'a mut self, file_name: &str) -> io::Result<Box<dyn Read + 'a>>;
+}
+
+impl Reader for ZipArchive<fs::File> {
+	fn file_names(&self) -> Vec<String> {
+		ZipArchive::file_names(self).map(String::from).collect()
+	}
+
+	fn file_size(&mut self, file_name: &str) -> Option<u64> {
+		self.by_name(file_name).ok().map(|zip_file| zip_file.size())
+	}
+
+	fn read<'a>(&'a mut self, file_name: &str) -> io::Result<Box<dyn Read + 'a>> {
+		Ok(Box::new(self.by_name(file_name)?))
+	}
+}
+
+impl Reader for TarArchive {
+	fn file_names(&self) -> Vec<String> {
+		TarArchive::file_names(self).map(String::from).collect()
+	}
+
+	fn file_size(&mut self, file_name: &str) -> Option<u64> {
+		self.size(file_name)
+	}
+
+	fn read<'a>(&'a mut self, file_name: &str) -> io::Result<Box<dyn Read + 'a>> {
+		Ok(Box::new(self.by_name(file_name)?))
+	}
+}
+
+impl Reader for SevenZArchive {
+	fn file_names(&self) -> Vec<String> {
+		SevenZArchive::file_names(self).map(String::from).collect()
+	}
+
+	fn file_size(&mut self, file_name: &str) -> Option<u64> {
+		self.size(file_name)
+	}
+
+	fn read<'a>(&'a mut self, file_name: &str) -> io::Result<Box<dyn Read + 'a>> {
+		Ok(Box::new(self.by_name(file_name)?))
+	}
+}
+
+/// Where archive files, such as `data/tweets_media/*`, may be read from
+pub enum Archive {
+	/// Zip files as downloaded from Twitter/X, or tar files they were repacked as
+	Packed {
+		/// Every part of archive, in order given
+		parts: RefCell<Vec<Box<dyn Reader>>>,
+
+		/// Map of file name to index of first part that holds it
+		index: HashMap<String, usize>,
+	},
+
+	/// Directory that archive was unpacked/extracted into
+	Directory(path::PathBuf),
+
+	/// Several exports of one account, newest first, each file is read from first that holds it
+	Merged(Vec<Archive>),
+}
+
+impl Archive {
+	/// Open every packed file part at `packed_paths` for repeated reads
+	pub fn packed(packed_paths: &[path::PathBuf]) -> io::Result<Self> {
+		let parts = packed_paths
+			.iter()
+			.map(|packed_path| open_reader(packed_path))
+			.collect::<io::Result<Vec<Box<dyn Reader>>>>()?;
+
+		Ok(Self::from_parts(parts))
+	}
+
+	/// Index file names of packed file `parts` that are already open, in order given
+	pub fn from_parts(parts: Vec<Box<dyn Reader>>) -> Self {
+		let mut index: HashMap<String, usize> = HashMap::new();
+
+		for (part, reader) in parts.iter().enumerate() {
+			for file_name in reader.file_names() {
+				index.entry(file_name).or_insert(part);
+			}
+		}
+
+		Self::Packed {
+			parts: RefCell::new(parts),
+			index,
+		}
+	}
+
+	/// Give back open packed file parts, eg. to combine with other parts of same archive, which
+	/// unpacked directories have none of
+	pub fn into_parts(self) -> Vec<Box<dyn Reader>> {
+		match self {
+			Self::Packed { parts, .. } => parts.into_inner(),
+			Self::Directory(_) => vec![],
+			Self::Merged(archives) => archives.into_iter().flat_map(Self::into_parts).collect(),
+		}
+	}
+
+	/// Open either unpacked directory, or every packed file part, found at `input_paths`
+	pub fn open(input_paths: &[path::PathBuf]) -> io::Result<Self> {
+		match input_paths {
+			[input_path] if input_path.is_dir() => Ok(Self::directory(input_path)),
+			_ => Self::packed(input_paths),
+		}
+	}
+
+	/// Use `directory` as root for relative file names
+	pub fn directory(directory: &path::Path) -> Self {
+		Self::Directory(directory.to_path_buf())
+	}
+
+	/// List relative file names, eg. `data/tweets_media/123-abc.jpg`, directly under `directory`
+	pub fn file_names(&self, directory: &str) -> Vec<String> {
+		let prefix = format!("{}/", directory.trim_end_matches('/'));
+
+		match self {
+			Self::Packed { index, .. } => {
+				let mut file_names = index
+					.keys()
+					.filter(|name| name.starts_with(&prefix) && !name[prefix.len()..].contains('/'))
+					.filter(|name| name.len() > prefix.len())
+					.cloned()
+					.collect::<Vec<String>>();
+				file_names.sort();
+				file_names
+			}
+			Self::Directory(root) => {
+				let mut directory_path = root.clone();
+				directory.split('/').for_each(|p| {
+					directory_path.push(p);
+				});
+
+				match fs::read_dir(&directory_path) {
+					Ok(entries) => entries
+						.filter_map(Result::ok)
+						.filter(|entry| entry.path().is_file())
+						.map(|entry| format!("{prefix}{}", entry.file_name().to_string_lossy()))
+						.collect(),
+					Err(_) => vec![],
+				}
+			}
+			Self::Merged(archives) => {
+				let mut file_names = archives
+					.iter()
+					.flat_map(|archive| archive.file_names(directory))
+					.collect::<Vec<String>>();
+				file_names.sort();
+				file_names.dedup();
+				file_names
+			}
+		}
+	}
+
+	/// Write content of archive `file_name` to `destination` file path
+	pub fn copy(&self, file_name: &str, destination: &path::Path) -> io::Result<u64> {
+		match self {
+			Self::Packed { parts, index } => {
+				let mut parts = parts.borrow_mut();
+				let mut reader = parts[packed_part(index, file_name)?].read(file_name)?;
+				let mut output = fs::File::create(destination)?;
+				io::copy(&mut reader, &mut output)
+			}
+			Self::Directory(_) => fs::copy(self.path(file_name), destination),
+			Self::Merged(archives) => {
+				merged_part(archives, file_name)?.copy(file_name, destination)
+			}
+		}
+	}
+
+	/// Uncompressed size, in bytes, of archive `file_name`, or `None` when it does not exist
+	pub fn file_size(&self, file_name: &str) -> Option<u64> {
+		match self {
+			Self::Packed { parts, index } => {
+				let part = packed_part(index, file_name).ok()?;
+				parts.borrow_mut()[part].file_size(file_name)
+			}
+			Self::Directory(_) => fs::metadata(self.path(file_name))
+				.ok()
+				.filter(|metadata| metadata.is_file())
+				.map(|metadata| metadata.len()),
+			Self::Merged(archives) => archives
+				.iter()
+				.find_map(|archive| archive.file_size(file_name)),
+		}
+	}
+
+	/// Load content of archive `file_name` into returned String
+	pub fn read_to_string(&self, file_name: &str) -> io::Result<String> {
+		match self {
+			Self::Packed { parts, index } => {
+				let mut parts = parts.borrow_mut();
+				let mut reader = parts[packed_part(index, file_name)?].read(file_name)?;
+				let mut buffer = String::new();
+				reader.read_to_string(&mut buffer)?;
+				Ok(buffer)
+			}
+			Self::Directory(_) => fs::read_to_string(self.path(file_name)),
+			Self::Merged(archives) => merged_part(archives, file_name)?.read_to_string(file_name),
+		}
+	}
+
+	/// Parse `data/manifest.js` after removing JavaScript `window.__THAR_CONFIG = ` assignment
+	pub fn manifest(&self) -> io::Result<manifest::Manifest> {
+		let javascript_manifest = self.read_to_string("data/manifest.js")?;
+		let (_, json_manifest) = strip_assignment(&javascript_manifest);
+
+		serde_json::from_str(json_manifest).map_err(|error| {
+			io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("Unable to parse data/manifest.js as JSON -> {error}"),
+			)
+		})
+	}
+
+	/// Same as `manifest` but untyped, so data types unknown to, or missing fields required by,
+	/// `twitter_archive` structs are kept
+	pub fn manifest_value(&self) -> io::Result<serde_json::Value> {
+		let javascript_manifest = self.read_to_string("data/manifest.js")?;
+		let (_, json_manifest) = strip_assignment(&javascript_manifest);
+
+		parse_json("data/manifest.js", json_manifest)
+	}
+
+	/// Parse every manifest listed file, eg. `data/note-tweet.js`, after removing JavaScript
+	/// `window.<global_name> = ` assignment, and concatenate results
+	pub fn read_data<T: DeserializeOwned>(
+		&self,
+		files: &[manifest::File],
+		args: &Args,
+	) -> io::Result<Vec<T>> {
+		Ok(self
+			.read_data_by_file(files, args)?
+			.into_iter()
+			.flat_map(|(_, data)| data)
+			.collect())
+	}
+
+	/// Same as `read_data` but keeps results of each file apart, paired with that file's name
+	pub fn read_data_by_file<T: DeserializeOwned>(
+		&self,
+		files: &[manifest::File],
+		args: &Args,
+	) -> io::Result<Vec<(String, Vec<T>)>> {
+		let mut result: Vec<(String, Vec<T>)> = vec![];
+
+		for file in files {
+			let javascript = self.read_to_string(&file.file_name)?;
+			let (identifier, json) = strip_assignment(&javascript);
+
+			if args.verbose {
+				eprintln!(
+					"archive::read_data:\n  file_name: {}\n  global_name: {}\n  identifier: {}",
+					file.file_name,
+					file.global_name,
+					identifier.unwrap_or_default()
+				);
+			}
+
+			let data: Vec<T> = parse_json(&file.file_name, json)?;
+
+			result.push((file.file_name.clone(), data));
+		}
+
+		Ok(result)
+	}
+
+	/// Join `/` separated archive `file_name` onto directory root
+	fn path(&self, file_name: &str) -> path::PathBuf {
+		let mut result = match self {
+			Self::Packed { .. } | Self::Merged(_) => path::PathBuf::new(),
+			Self::Directory(root) => root.clone(),
+		};
+
+		file_name.split('/').for_each(|p| {
+			result.push(p);
+		});
+
+		result
+	}
+}
+
+/// First of merged `archives` that holds `file_name`
+fn merged_part<'a>(archives: &'a [Archive], file_name: &str) -> io::Result<&'a Archive> {
+	archives
+		.iter()
+		.find(|archive| archive.file_size(file_name).is_some())
+		.ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::NotFound,
+				format!("No merged archive holds {file_name}"),
+			)
+		})
+}
+
+/// Split JavaScript assignment, eg. `window.YTD.tweets.part0 = [ ... ]`, into identifier after
+/// `window.` and JSON value assigned to it
+///
+/// Byte order marks, surrounding white-space, CRLF line endings, and trailing semicolons are
+/// tolerated, while text without a `window.<identifier> =` prefix is returned as is for parsing
+/// as plain JSON.
+///
+/// ## Example
+///
+/// ```ignore
+/// let (identifier, json) = strip_assignment("\u{feff}window.YTD.tweets.part1= [];\r\n");
+/// assert_eq!(identifier, Some("YTD.tweets.part1"));
+/// assert_eq!(json, "[]");
+/// ```
+pub fn strip_assignment(javascript: &str) -> (Option<&str>, &str) {
+	let javascript = javascript
+		.trim_start_matches('\u{feff}')
+		.trim()
+		.trim_end_matches(|c: char| c == ';' || c.is_whitespace());
+
+	let Some(assignment) = javascript.strip_prefix("window.") else {
+		return (None, javascript);
+	};
+
+	let identifier_length = assignment
+		.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$' || c == '.'))
+		.unwrap_or(assignment.len());
+	let (identifier, value) = assignment.split_at(identifier_length);
+
+	match value.trim_start().strip_prefix('=') {
+		Some(json) if !identifier.is_empty() => (Some(identifier), json.trim_start()),
+		_ => (None, javascript),
+	}
+}
+
+/// Parse `json` stripped from `file_name`, naming that file within any error
+pub fn parse_json<T: DeserializeOwned>(file_name: &str, json: &str) -> io::Result<T> {
+	serde_json::from_str(json).map_err(|error| {
+		io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("Unable to parse {file_name} as JSON -> {error}"),
+		)
+	})
+}
+
+/// Parse `section` of untyped `data_manifest`, eg. `archiveInfo`, on its own so problems elsewhere
+/// within manifest, such as data types `twitter_archive` requires but are missing, do not prevent
+/// reading it
+pub fn manifest_section<T: DeserializeOwned>(
+	data_manifest: &serde_json::Value,
+	section: &str,
+) -> io::Result<T> {
+	let value = data_manifest.get(section).cloned().ok_or_else(|| {
+		io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("No {section} within data/manifest.js"),
+		)
+	})?;
+
+	serde_json::from_value(value).map_err(|error| {
+		io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("Unable to parse {section} of data/manifest.js -> {error}"),
+		)
+	})
+}
+
+/// Data type named by assignment identifier, eg. `note_tweet` for `YTD.note_tweet.part2`
+pub fn data_type_of(identifier: &str) -> Option<&str> {
+	let (data_type, part) = identifier.strip_prefix("YTD.")?.rsplit_once(".part")?;
+
+	part.parse::<usize>().ok().map(|_| data_type)
+}
+
+/// Index of packed file part that holds `file_name`
+fn packed_part(index: &HashMap<String, usize>, file_name: &str) -> io::Result<usize> {
+	index.get(file_name).copied().ok_or_else(|| {
+		io::Error::new(
+			io::ErrorKind::NotFound,
+			format!("No part of packed archive holds {file_name}"),
+		)
+	})
+}
+
+/// Whether `file_path` names a packed archive format, eg. `.zip` or `.tar.gz`, by its suffix
+pub fn is_packed(file_path: &path::Path) -> bool {
+	let file_name = file_path
+		.file_name()
+		.map(|name| name.to_string_lossy().to_lowercase())
+		.unwrap_or_default();
+
+	file_name.ends_with(".zip")
+		|| file_name.ends_with(".7z")
+		|| tar::compression_of(&file_name).is_some()
+}
+
+/// Open reader for format that suffix of `packed_path` names
+fn open_reader(packed_path: &path::Path) -> io::Result<Box<dyn Reader>> {
+	let file_name = packed_path
+		.file_name()
+		.map(|name| name.to_string_lossy().to_lowercase())
+		.unwrap_or_default();
+
+	if let Some(compression) = tar::compression_of(&file_name) {
+		return Ok(Box::new(TarArchive::new(packed_path, compression)?));
+	}
+
+	if file_name.ends_with(".7z") {
+		return Ok(Box::new(SevenZArchive::new(packed_path)?));
+	}
+
+	Ok(Box::new(ZipArchive::new(fs::File::open(packed_path)?)?))
+}
+
+/// List paths named by `--input-path` values, values with `*` or `?` wildcards within their file
+/// name are replaced by every matching path, sorted so `part2` follows `part1` and precedes
+/// `part10`
+pub fn expand_input_paths(input_paths: &[String]) -> Vec<path::PathBuf> {
+	let mut result: Vec<path::PathBuf> = vec![];
+
+	for input_path in input_paths {
+		let input_path = path::Path::new(input_path);
+		let pattern = input_path
+			.file_name()
+			.map(|name| name.to_string_lossy().to_string())
+			.unwrap_or_default();
+
+		if !pattern.contains(['*', '?']) {
+			result.push(input_path.to_path_buf());
+			continue;
+		}
+
+		let directory = match input_path.parent() {
+			Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+			_ => path::PathBuf::from("."),
+		};
+
+		let mut matches = fs::read_dir(&directory)
+			.map(|entries| {
+				entries
+					.filter_map(Result::ok)
+					.filter(|entry| wildcard_match(&pattern, &entry.file_name().to_string_lossy()))
+					.map(|entry| directory.join(entry.file_name()))
+					.collect::<Vec<path::PathBuf>>()
+			})
+			.unwrap_or_default();
+		matches.sort_by_key(|file_path| {
+			let file_name = file_path
+				.file_name()
+				.map(|name| name.to_string_lossy().to_string())
+				.unwrap_or_default();
+			match split_part_of(&file_name) {
+				Some((base_name, part)) => (base_name.to_string(), part, file_name),
+				None => (file_name.clone(), 0, file_name),
+			}
+		});
+		result.extend(matches);
+	}
+
+	result
+}
+
+/// Archive base name, and part number, of split archive `file_name`, eg. `twitter-2023-08-31-abc`
+/// and `2` for `twitter-2023-08-31-abc-part2.zip`
+pub fn split_part_of(file_name: &str) -> Option<(&str, usize)> {
+	let (base_name, rest) = file_name.rsplit_once("-part")?;
+	let part = rest
+		.chars()
+		.take_while(char::is_ascii_digit)
+		.collect::<String>();
+
+	part.parse().ok().map(|part| (base_name, part))
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters and `?` any one
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+	let pattern = pattern.chars().collect::<Vec<char>>();
+	let name = name.chars().collect::<Vec<char>>();
+
+	// Position within pattern after last `*`, and position within name it was matched up to
+	let mut star: Option<(usize, usize)> = None;
+	let (mut p, mut n) = (0, 0);
+
+	while n < name.len() {
+		if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+			p += 1;
+			n += 1;
+		} else if p < pattern.len() && pattern[p] == '*' {
+			star = Some((p + 1, n));
+			p += 1;
+		} else if let Some((after_star, matched)) = star {
+			p = after_star;
+			n = matched + 1;
+			star = Some((after_star, matched + 1));
+		} else {
+			return false;
+		}
+	}
+
+	pattern[p..].iter().all(|c| *c == '*')
+}
@@ -1,9 +1,9 @@
 #!/usr/bin/env rust
 
-use clap::{CommandFactory, Parser};
+use clap::{CommandFactory, Parser, ValueEnum};
 use clap_complete::{Generator, Shell};
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[clap(author, version)]
 #[clap(about, verbatim_doc_comment)]
 #[clap(arg_required_else_help = true)]
@@ -66,21 +66,50 @@ pub struct Args {
 	/// tweet-archive-to-markdown --input-path "./twitter.archive.zip"
 	/// ```
 	///
-	/// [possible file extensions: zip, js, json]
+	/// [possible file extensions: zip, tar, tar.gz, tgz, tar.zst, tzst, 7z, js, json]
 	///
 	/// > zip -- attempt to read `./twitter.archive.zip::data/manifest.js` and parse all relative
 	/// > paths to `data/tweets.js` into JSON, then writes MarkDown files
 	/// >
-	/// > js -- use `--javascript-pattern` value to convert JavaScript into JSON, then will attempt
-	/// > to write MarkDown files
+	/// > tar, tar.gz, tgz, tar.zst, tzst, 7z -- same as zip, for archives repacked as tar or 7z
+	/// > files, compressed ones are decompressed once into a temporary file
+	/// >
+	/// > js -- strip `window.<identifier> = ` assignment, or `--javascript-pattern` value, to
+	/// > convert JavaScript into JSON, then will attempt to write MarkDown files
 	/// >
 	/// > json -- assumes you have already converted JavaScript into JSON, and will attempt to
 	/// > write MarkDown files from parsed JSON data
 	/// >
 	/// > Directory -- assumes archive was unpacked/extracted and `data/manifest.js` file, and
-	/// > files it points to may be found under `--input-path` value
-	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::FilePath)]
-	pub input_path: String,
+	/// > files it points to may be found under `--input-path` value, or lacking a manifest that
+	/// > loose `.js` files, eg. `tweets.js` and `note-tweet.js`, are found directly within it
+	///
+	/// ## Example input of archive split across several zip files
+	///
+	/// ```
+	/// tweet-archive-to-markdown --input-path "./twitter-part1.zip" "./twitter-part2.zip"
+	/// tweet-archive-to-markdown --input-path "./twitter-*-part*.zip"
+	/// ```
+	///
+	/// > Every part is read as one archive, quote `*` and `?` wildcards so they match file names
+	/// > here instead of being expanded by shell
+	///
+	/// ## Example input of several exports of one account
+	///
+	/// ```
+	/// tweet-archive-to-markdown --input-path "./twitter-2019.zip" "./twitter-2023.zip"
+	/// ```
+	///
+	/// > Tweets of every export are merged by `id_str`, where exports disagree the one with most
+	/// > recent manifest `generationDate` wins for each field
+	#[arg(
+		long,
+		verbatim_doc_comment,
+		required = true,
+		num_args = 1..,
+		value_hint = clap::ValueHint::FilePath
+	)]
+	pub input_path: Vec<String>,
 
 	/// Path to directory where MarkDown files will be written
 	///
@@ -94,21 +123,19 @@ pub struct Args {
 	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::DirPath, required = false)]
 	pub output_directory: Option<String>,
 
-	/// Useful if/when `--input-path` targets an explicit `data/tweets.js` file path
+	/// Prefix to remove when `--input-path` targets a `.js` file not starting with a
+	/// `window.<identifier> = ` assignment, such as `window.YTD.tweets.part0 = `
 	///
 	/// ## Example
 	///
 	/// ```
-	/// tweet-archive-to-markdown --javascript-pattern "window.YTD.tweets.part0"
+	/// tweet-archive-to-markdown --javascript-pattern "var tweets = "
 	/// ```
-	#[arg(
-		long,
-		verbatim_doc_comment,
-		required = false,
-		default_value = "window.YTD.tweets.part0"
-	)]
-	#[clap(value_enum)]
-	pub javascript_pattern: String,
+	///
+	/// > Any `window.<identifier> = ` assignment is recognised without this option, even when file
+	/// > starts with a byte order mark or ends with a semicolon
+	#[arg(long, verbatim_doc_comment, required = false)]
+	pub javascript_pattern: Option<String>,
 
 	/// Custom format string for `DateTime` output parsed from `.tweets[].tweet.created_at` used to
 	/// generate post FrontMatter for `date` YAML
@@ -134,6 +161,8 @@ pub struct Args {
 	/// ```
 	/// tweet-archive-to-markdown --post-author "S0AndS0"
 	/// ```
+	///
+	/// [default: archive manifest `userInfo.userName`]
 	#[arg(long, verbatim_doc_comment, required = false)]
 	#[clap(value_enum)]
 	pub post_author: Option<String>,
@@ -165,6 +194,410 @@ pub struct Args {
 	#[clap(value_enum)]
 	pub post_twitter_key: String,
 
+	/// Directory to copy Tweet media into, from archive `data/tweets_media`, and embed within posts
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --media-directory "./assets/tweets"
+	/// ```
+	///
+	/// > Note; when undefined media remain links to `twitter.com`
+	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::DirPath, required = false)]
+	pub media_directory: Option<String>,
+
+	/// URL path prepended to copied media file names within posts
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --media-url-prefix "/assets/tweets"
+	/// ```
+	///
+	/// [default: `--media-directory` relative to `--output-directory`]
+	#[arg(long, verbatim_doc_comment, required = false)]
+	pub media_url_prefix: Option<String>,
+
+	/// Format of links to archived Tweets that replies, quotes, and shared links should use instead
+	/// of `twitter.com`, `{id}` is replaced by first Tweet ID of post and remaining text is
+	/// formatted with date of that Tweet
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --post-permalink "/tweets/%Y/%m/%d/{id}/"
+	/// ```
+	///
+	/// [default: relative MarkDown file name, eg. `2023-08-30-1697011324369178968.md`]
+	#[arg(long, verbatim_doc_comment, required = false)]
+	pub post_permalink: Option<String>,
+
+	/// How old-style retweets, with text starting `RT @user:`, should be written
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --retweets link
+	/// ```
+	///
+	/// > skip -- do not write posts for retweets
+	/// >
+	/// > link -- write minimal posts that only link to retweeted Tweet
+	/// >
+	/// > include -- write posts that quote retweeted text with attribution to original author
+	#[arg(
+		long,
+		verbatim_doc_comment,
+		required = false,
+		default_value = "include"
+	)]
+	#[clap(value_enum)]
+	pub retweets: RetweetPolicy,
+
+	/// How earlier versions of edited Tweets should be written
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --edits history
+	/// ```
+	///
+	/// > front-matter -- only write latest version, listing earlier versions under `edits`
+	/// >
+	/// > history -- same as `front-matter` and append collapsible "Edit history" section
+	/// >
+	/// > separate -- write one file per version
+	#[arg(
+		long,
+		verbatim_doc_comment,
+		required = false,
+		default_value = "front-matter"
+	)]
+	#[clap(value_enum)]
+	pub edits: EditPolicy,
+
+	/// Combine each chain of self-replies into one post, named after the first Tweet of thread
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --threads
+	/// ```
+	#[arg(long, verbatim_doc_comment, required = false)]
+	pub threads: bool,
+
+	/// Subdirectory of `--output-directory` where posts of Tweets made within X Communities, as
+	/// listed by archive `data/community-tweet.js`, will be written
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --community-directory "communities"
+	/// ```
+	///
+	/// [default: `--output-directory`]
+	#[arg(long, verbatim_doc_comment, required = false)]
+	pub community_directory: Option<String>,
+
+	/// Name of X Community to list within FrontMatter of its posts, archives only provide an ID
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --community-name "1498728462582538240=Rust Programming"
+	/// ```
+	///
+	/// > Note; may be repeated once per community
+	#[arg(long, verbatim_doc_comment, required = false, value_name = "ID=NAME")]
+	pub community_name: Vec<String>,
+
+	/// Add `provenance` FrontMatter, listing archive generation date, source data file, and
+	/// account ID, so published posts may be traced back to the export they came from
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --provenance
+	/// ```
+	#[arg(long, verbatim_doc_comment, required = false)]
+	pub provenance: bool,
+
+	/// Whether Tweets listed by archive `data/deleted-tweets.js` should be written
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --deleted-tweets tombstone
+	/// ```
+	///
+	/// > skip -- do not write posts for deleted Tweets
+	/// >
+	/// > include -- write posts with text and `deleted_at` FrontMatter
+	/// >
+	/// > tombstone -- write stubs that only list Tweet ID and dates, without text or media
+	#[arg(long, verbatim_doc_comment, required = false, default_value = "skip")]
+	#[clap(value_enum)]
+	pub deleted_tweets: DeletedPolicy,
+
+	/// Path to directory where MarkDown files of deleted Tweets will be written, kept apart from
+	/// `--output-directory` so they are not published by accident
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --deleted-output-directory "~/Documents/_deleted_tweets"
+	/// ```
+	///
+	/// > Note; required when `--deleted-tweets` is `include` or `tombstone`, and refused when
+	/// > within `--output-directory` or `--media-directory`
+	#[arg(
+		long,
+		verbatim_doc_comment,
+		value_hint = clap::ValueHint::DirPath,
+		required_if_eq_any = [("deleted_tweets", "include"), ("deleted_tweets", "tombstone")]
+	)]
+	pub deleted_output_directory: Option<String>,
+
+	/// Post `layout` FrontMatter value for MarkDown files of deleted Tweets
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --deleted-post-layout "deleted_tweet"
+	/// ```
+	#[arg(
+		long,
+		verbatim_doc_comment,
+		required = false,
+		default_value = "deleted_tweet"
+	)]
+	pub deleted_post_layout: String,
+
+	/// How liked Tweets, listed by archive `data/like.js`, should be written
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --likes month
+	/// ```
+	///
+	/// > skip -- do not write likes
+	/// >
+	/// > each -- write one file per like, dated by liked Tweet ID
+	/// >
+	/// > month -- write one file per month, listing every like of that month
+	#[arg(long, verbatim_doc_comment, required = false, default_value = "skip")]
+	#[clap(value_enum)]
+	pub likes: LikesPolicy,
+
+	/// Path to directory where MarkDown files of likes will be written
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --likes-output-directory "./_likes"
+	/// ```
+	///
+	/// [default: `--output-directory` with `_likes` appended, eg. `_tweets_likes`]
+	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::DirPath, required = false)]
+	pub likes_output_directory: Option<String>,
+
+	/// Post `layout` FrontMatter value for MarkDown files of likes
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --likes-post-layout "like"
+	/// ```
+	#[arg(long, verbatim_doc_comment, required = false, default_value = "like")]
+	pub likes_post_layout: String,
+
+	/// Path to private directory where one MarkDown transcript per Direct Message conversation,
+	/// and attached media, will be written
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --direct-messages-directory "~/Documents/direct-messages"
+	/// ```
+	///
+	/// > Note; Direct Messages are only written when defined, and never within
+	/// > `--output-directory` or `--media-directory`
+	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::DirPath, required = false)]
+	pub direct_messages_directory: Option<String>,
+
+	/// Path of MarkDown file to write account profile, eg. bio, website, avatar, and handle
+	/// history, into
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --profile-page "about.md"
+	/// ```
+	///
+	/// > Note; avatar and header images are copied into `--media-directory` when defined, while
+	/// > without `--output-directory` only profile page, and any `--dump-directory`, is written
+	/// > and no posts
+	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::FilePath, required = false)]
+	pub profile_page: Option<String>,
+
+	/// Post `layout` FrontMatter value for MarkDown file of profile
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --profile-layout "profile"
+	/// ```
+	#[arg(
+		long,
+		verbatim_doc_comment,
+		required = false,
+		default_value = "profile"
+	)]
+	pub profile_layout: String,
+
+	/// URL that mentions of account's current, or former, handles link to instead of Twitter
+	/// [default: `--profile-page` relative to post]
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --profile-url "/about"
+	/// ```
+	#[arg(long, verbatim_doc_comment, required = false)]
+	pub profile_url: Option<String>,
+
+	/// Path to directory where followers and following, joined with mentions of each account,
+	/// will be written as `graph.csv`, `graph.json`, and `graph.graphml`
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --graph-directory "~/Documents/twitter-graph"
+	/// ```
+	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::DirPath, required = false)]
+	pub graph_directory: Option<String>,
+
+	/// Subdirectory of `--output-directory` where one MarkDown page per Moment, from
+	/// `data/moment.js`, will be written
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --moments-directory "moments"
+	/// ```
+	///
+	/// > Note; member Tweets found within archive link to their posts, others link to Twitter
+	#[arg(long, verbatim_doc_comment, required = false)]
+	pub moments_directory: Option<String>,
+
+	/// Path to private directory where every data type listed by archive manifest, eg. blocks,
+	/// mutes, and ad engagements, will be written as one file per data type
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --dump-directory "~/Documents/twitter-dump"
+	/// ```
+	///
+	/// > Note; data types are only written when defined, and never within `--output-directory`
+	/// > or `--media-directory`, while without `--output-directory` only data types are
+	/// > written and no posts
+	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::DirPath, required = false)]
+	pub dump_directory: Option<String>,
+
+	/// How each data type is written into `--dump-directory`
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --dump-format json
+	/// ```
+	///
+	/// > markdown -- write a MarkDown table per data type, one row per entry
+	/// >
+	/// > json -- write a JSON array per data type, entries as found within archive
+	#[arg(
+		long,
+		verbatim_doc_comment,
+		required = false,
+		default_value = "markdown"
+	)]
+	#[clap(value_enum)]
+	pub dump_format: DumpFormat,
+
+	/// Path of MarkDown file listing every account when `--input-path` values hold archives of
+	/// several accounts, whose posts are each written within a subdirectory of
+	/// `--output-directory` named after account handle
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --input-path "./alice.zip" "./bob.zip" --accounts-index "accounts.md"
+	/// ```
+	///
+	/// > Note; `{user_name}` within `--post-author`, `--post-twitter-key`, `--post-permalink`,
+	/// > and paths of other exports, eg. `--profile-page "about/{user_name}.md"`, is replaced by
+	/// > handle of each account, so nothing of one account overwrites another
+	/// >
+	/// > Without `{user_name}` within `--post-twitter-key` the handle is appended, eg.
+	/// > `twitter_S0_And_S0`
+	#[arg(long, verbatim_doc_comment, value_hint = clap::ValueHint::FilePath, required = false)]
+	pub accounts_index: Option<String>,
+
+	/// Post `layout` FrontMatter value for MarkDown file of `--accounts-index`
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --accounts-layout "accounts"
+	/// ```
+	#[arg(
+		long,
+		verbatim_doc_comment,
+		required = false,
+		default_value = "accounts"
+	)]
+	pub accounts_layout: String,
+
+	/// Report files, record counts, and sizes of every data type listed by archive manifest to
+	/// standard out then exit, without converting anything
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --input-path ~/Downloads/twitter-archive.zip --audit json
+	/// ```
+	///
+	/// > text -- aligned table with a line per missing, or unparsable, file
+	/// >
+	/// > json -- same report as JSON object
+	/// >
+	/// > Note; exit status is non-zero when any file is missing, unparsable, or holds a different
+	/// > number of records than declared
+	#[arg(long, verbatim_doc_comment, required = false)]
+	#[clap(value_enum)]
+	pub audit: Option<ReportFormat>,
+
+	/// Report Tweet fields that archive holds but this tool ignores, fields it expects but
+	/// archive lacks, and fields of unexpected type, to standard out then exit
+	///
+	/// ## Example
+	///
+	/// ```
+	/// tweet-archive-to-markdown --input-path ~/Downloads/twitter-archive.zip --schema-check text
+	/// ```
+	///
+	/// > text -- list of fields with occurrence counts and example Tweet IDs
+	/// >
+	/// > json -- same report as JSON object
+	/// >
+	/// > Note; exit status is non-zero when any Tweet fails to parse, lacks expected fields, or
+	/// > has fields of unexpected type, ignored fields only lose data
+	#[arg(long, verbatim_doc_comment, required = false)]
+	#[clap(value_enum)]
+	pub schema_check: Option<ReportFormat>,
+
 	/// Output shell completions to standard out then exit
 	///
 	/// ## Example
@@ -197,6 +630,78 @@ pub struct Args {
 	pub verbose: bool,
 }
 
+/// Choices for `--retweets` option
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetweetPolicy {
+	/// Do not write posts for retweets
+	Skip,
+
+	/// Write minimal posts that only link to retweeted Tweet
+	Link,
+
+	/// Write posts that quote retweeted text with attribution to original author
+	Include,
+}
+
+/// Choices for `--edits` option
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditPolicy {
+	/// Only write latest version, listing earlier versions within FrontMatter
+	FrontMatter,
+
+	/// Same as `FrontMatter` and append collapsible edit history to content
+	History,
+
+	/// Write one file per version
+	Separate,
+}
+
+/// Choices for `--deleted-tweets` option
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeletedPolicy {
+	/// Do not write posts for deleted Tweets
+	Skip,
+
+	/// Write posts with text and `deleted_at` FrontMatter
+	Include,
+
+	/// Write stubs that only list Tweet ID and dates
+	Tombstone,
+}
+
+/// Choices for `--likes` option
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LikesPolicy {
+	/// Do not write likes
+	Skip,
+
+	/// Write one file per like
+	Each,
+
+	/// Write one file per month of likes
+	Month,
+}
+
+/// Choices for `--audit` and `--schema-check` options
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+	/// Human readable text
+	Text,
+
+	/// JSON object
+	Json,
+}
+
+/// Choices for `--dump-format` option
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DumpFormat {
+	/// Write a MarkDown table per data type
+	Markdown,
+
+	/// Write a JSON array per data type
+	Json,
+}
+
 /// Display tab-completion configuration for given shell
 ///
 /// ## Resources for further reading
@@ -0,0 +1,350 @@
+#!/usr/bin/env rust
+
+//! Report what archive manifest declares against what archive actually holds, so missing or
+//! truncated files, eg. `data/tweets-part3.js`, are found before converting anything
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{io, path};
+use twitter_archive::structs::manifest::{self, ArchiveInfo, Manifest, UserInfo};
+
+use crate::archive::{self, Archive};
+use crate::arguments::{Args, ReportFormat};
+
+/// Whole report of archive
+#[derive(Serialize, Debug)]
+struct Audit {
+	/// Zip files, or directory, that were audited
+	input_path: String,
+
+	/// ID of account that requested archive, `None` when manifest `userInfo` is unreadable
+	account_id: Option<String>,
+
+	/// Handle of account that requested archive
+	user_name: Option<String>,
+
+	/// When archive was generated, `None` when manifest `archiveInfo` is unreadable
+	generation_date: Option<DateTime<Utc>>,
+
+	/// Size of archive declared by manifest
+	size_bytes: Option<usize>,
+
+	/// Whether archive was split into multiple parts
+	is_partial_archive: Option<bool>,
+
+	/// Problems of manifest itself, eg. unparsable JSON, or data types `twitter_archive` requires
+	/// but are missing, which would prevent converting archive
+	problems: Vec<String>,
+
+	/// Every data type that lists files or a media directory
+	data_types: Vec<DataTypeAudit>,
+}
+
+/// Report of single manifest data type, eg. `tweets`
+#[derive(Serialize, Debug)]
+struct DataTypeAudit {
+	/// Name of data type as written within manifest, eg. `directMessages`
+	name: String,
+
+	/// Sum of records declared by manifest
+	declared: usize,
+
+	/// Sum of records parsed from files that exist
+	parsed: usize,
+
+	/// Sum of uncompressed sizes of files that exist
+	size_bytes: u64,
+
+	/// Directory of media files, if data type has one
+	#[serde(skip_serializing_if = "Option::is_none")]
+	media_directory: Option<String>,
+
+	/// Number of files found under `media_directory`
+	#[serde(skip_serializing_if = "Option::is_none")]
+	media_files: Option<usize>,
+
+	/// Report of each file listed for data type
+	files: Vec<FileAudit>,
+}
+
+/// Report of single file listed by manifest, eg. `data/tweets-part1.js`
+#[derive(Serialize, Debug)]
+struct FileAudit {
+	/// Path of file within archive
+	file_name: String,
+
+	/// Whether file exists within archive
+	exists: bool,
+
+	/// Uncompressed size of file
+	#[serde(skip_serializing_if = "Option::is_none")]
+	size_bytes: Option<u64>,
+
+	/// Number of records declared by manifest
+	declared: usize,
+
+	/// Number of records parsed, if file could be parsed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	parsed: Option<usize>,
+
+	/// Why file could not be read or parsed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<String>,
+}
+
+impl FileAudit {
+	/// Whether file exists, parses, and holds as many records as declared
+	fn is_healthy(&self) -> bool {
+		self.exists && self.parsed == Some(self.declared)
+	}
+}
+
+impl DataTypeAudit {
+	/// Short description of problems, if any, of data type
+	fn status(&self) -> &str {
+		if self.files.iter().any(|file| !file.exists) {
+			"missing"
+		} else if self.files.iter().any(|file| file.error.is_some()) {
+			"unparsable"
+		} else if self.parsed != self.declared {
+			"mismatch"
+		} else {
+			"ok"
+		}
+	}
+}
+
+/// Print report of archive at `input_paths` to standard out, returns whether manifest is readable
+/// and every listed file exists, parses, and holds as many records as declared
+///
+/// Manifest is read untyped, so problems within it are reported instead of ending the audit.
+pub fn print(
+	input_paths: &[path::PathBuf],
+	report_format: ReportFormat,
+	args: &Args,
+) -> io::Result<bool> {
+	let archive = Archive::open(input_paths)?;
+	let data_manifest = archive.manifest_value().map_err(|error| error.to_string());
+
+	let audit = audit(&archive, input_paths, data_manifest, args);
+	let healthy = audit.problems.is_empty()
+		&& audit
+			.data_types
+			.iter()
+			.flat_map(|data_type| &data_type.files)
+			.all(FileAudit::is_healthy);
+
+	match report_format {
+		ReportFormat::Text => print!("{}", text(&audit)),
+		ReportFormat::Json => println!(
+			"{}",
+			serde_json::to_string_pretty(&audit).expect("Unable to serialize audit as JSON")
+		),
+	}
+
+	Ok(healthy)
+}
+
+/// Check every file, and media directory, listed by manifest data types
+fn audit(
+	archive: &Archive,
+	input_paths: &[path::PathBuf],
+	data_manifest: Result<serde_json::Value, String>,
+	args: &Args,
+) -> Audit {
+	let mut problems: Vec<String> = vec![];
+	let data_manifest = data_manifest.unwrap_or_else(|error| {
+		problems.push(error);
+		serde_json::Value::Null
+	});
+
+	// Each section is read on its own, so one unreadable section still leaves others reported
+	let mut archive_info: Option<ArchiveInfo> = None;
+	let mut user_info: Option<UserInfo> = None;
+	if !data_manifest.is_null() {
+		archive_info = archive::manifest_section(&data_manifest, "archiveInfo")
+			.map_err(|error| problems.push(error.to_string()))
+			.ok();
+		user_info = archive::manifest_section(&data_manifest, "userInfo")
+			.map_err(|error| problems.push(error.to_string()))
+			.ok();
+
+		if let Err(error) = serde_json::from_value::<Manifest>(data_manifest.clone()) {
+			problems.push(format!(
+				"Unable to convert archive by its manifest -> {error}"
+			));
+		}
+	}
+
+	let mut result: Vec<DataTypeAudit> = vec![];
+	let data_types = data_manifest
+		.get("dataTypes")
+		.and_then(serde_json::Value::as_object);
+	for (name, data_type) in data_types.into_iter().flatten() {
+		let files = data_type
+			.get("files")
+			.and_then(serde_json::Value::as_array)
+			.cloned()
+			.unwrap_or_default();
+		let media_directory = data_type
+			.get("mediaDirectory")
+			.and_then(serde_json::Value::as_str)
+			.map(String::from);
+
+		if files.is_empty() && media_directory.is_none() {
+			continue;
+		}
+
+		let files = files
+			.iter()
+			.map(
+				|file| match serde_json::from_value::<manifest::File>(file.clone()) {
+					Ok(file) => audit_file(archive, &file, args),
+					Err(error) => FileAudit {
+						file_name: file
+							.get("fileName")
+							.and_then(serde_json::Value::as_str)
+							.unwrap_or("?")
+							.to_string(),
+						exists: false,
+						size_bytes: None,
+						declared: 0,
+						parsed: None,
+						error: Some(format!("Unable to parse manifest entry -> {error}")),
+					},
+				},
+			)
+			.collect::<Vec<FileAudit>>();
+
+		result.push(DataTypeAudit {
+			name: name.clone(),
+			declared: files.iter().map(|file| file.declared).sum(),
+			parsed: files.iter().filter_map(|file| file.parsed).sum(),
+			size_bytes: files.iter().filter_map(|file| file.size_bytes).sum(),
+			media_files: media_directory
+				.as_ref()
+				.map(|media_directory| archive.file_names(media_directory).len()),
+			media_directory,
+			files,
+		});
+	}
+
+	Audit {
+		input_path: input_paths
+			.iter()
+			.map(|input_path| input_path.display().to_string())
+			.collect::<Vec<String>>()
+			.join(" "),
+		account_id: user_info
+			.as_ref()
+			.map(|user_info| user_info.account_id.clone()),
+		user_name: user_info
+			.as_ref()
+			.map(|user_info| user_info.user_name.clone()),
+		generation_date: archive_info
+			.as_ref()
+			.map(|archive_info| archive_info.generation_date),
+		size_bytes: archive_info
+			.as_ref()
+			.map(|archive_info| archive_info.size_bytes),
+		is_partial_archive: archive_info
+			.as_ref()
+			.map(|archive_info| archive_info.is_partial_archive),
+		problems,
+		data_types: result,
+	}
+}
+
+/// Check that single `file` exists and count records it holds
+fn audit_file(archive: &Archive, file: &manifest::File, args: &Args) -> FileAudit {
+	let size_bytes = archive.file_size(&file.file_name);
+
+	let (parsed, error) = match size_bytes {
+		Some(_) => match archive.read_data::<serde_json::Value>(std::slice::from_ref(file), args) {
+			Ok(records) => (Some(records.len()), None),
+			Err(error) => (None, Some(error.to_string())),
+		},
+		None => (None, Some("No such file within archive".into())),
+	};
+
+	FileAudit {
+		file_name: file.file_name.clone(),
+		exists: size_bytes.is_some(),
+		size_bytes,
+		declared: file.count,
+		parsed,
+		error,
+	}
+}
+
+/// Aligned table of data types followed by a line per problem file
+///
+/// ## Example output
+///
+/// ```text
+/// Archive: twitter-2023-08-31-abc.zip
+/// Account: @S0AndS0 (111111111)
+/// Generated: 2023-08-31T01:00:23Z
+/// Size: 44546997 bytes
+///
+/// DATA TYPE                       FILES  DECLARED    PARSED       BYTES  MEDIA  STATUS
+/// tweets                              2      2000      1500     1234567     12  missing
+///   data/tweets-part1.js -> No such file within archive
+/// ```
+fn text(audit: &Audit) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(format!("Archive: {}", audit.input_path));
+	if let (Some(user_name), Some(account_id)) = (&audit.user_name, &audit.account_id) {
+		lines.push(format!("Account: @{user_name} ({account_id})"));
+	}
+	if let Some(generation_date) = audit.generation_date {
+		lines.push(format!("Generated: {}", generation_date.to_rfc3339()));
+	}
+	if let Some(size_bytes) = audit.size_bytes {
+		lines.push(format!(
+			"Size: {size_bytes} bytes{}",
+			match audit.is_partial_archive {
+				Some(true) => " (partial archive)",
+				_ => "",
+			}
+		));
+	}
+	for problem in &audit.problems {
+		lines.push(format!("Manifest: {problem}"));
+	}
+	lines.push(String::new());
+
+	lines.push(format!(
+		"{:<32}{:>5}{:>10}{:>10}{:>12}{:>7}  STATUS",
+		"DATA TYPE", "FILES", "DECLARED", "PARSED", "BYTES", "MEDIA"
+	));
+	for data_type in &audit.data_types {
+		lines.push(format!(
+			"{:<32}{:>5}{:>10}{:>10}{:>12}{:>7}  {}",
+			data_type.name,
+			data_type.files.len(),
+			data_type.declared,
+			data_type.parsed,
+			data_type.size_bytes,
+			data_type
+				.media_files
+				.map(|count| count.to_string())
+				.unwrap_or("-".into()),
+			data_type.status()
+		));
+
+		for file in data_type.files.iter().filter(|file| !file.is_healthy()) {
+			let problem = match (&file.error, file.parsed) {
+				(Some(error), _) => error.clone(),
+				(None, Some(parsed)) => {
+					format!("Declared {} records, parsed {parsed}", file.declared)
+				}
+				(None, None) => "Unknown problem".into(),
+			};
+			lines.push(format!("  {} -> {problem}", file.file_name));
+		}
+	}
+
+	format!("{}\n", lines.join("\n"))
+}
@@ -0,0 +1,314 @@
+#!/usr/bin/env rust
+
+//! Archive wide state, beyond CLI arguments, shared by functions that build posts
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path;
+use twitter_archive::structs::manifest::{ArchiveInfo, Manifest, UserInfo};
+
+use crate::archive::Archive;
+use crate::arguments::Args;
+use crate::media::{self, MediaIndex};
+use crate::post_build;
+use crate::structs::{Tweet, TweetMedia};
+
+/// Where an archived Tweet will be published
+#[derive(Debug, Clone)]
+pub struct LocalPost {
+	/// ID of first Tweet within post, differs from Tweet ID for later Tweets of a thread
+	pub root_id: String,
+
+	/// MarkDown file name of post, eg. `2023-08-30-1697011324369178968.md`
+	pub file_name: String,
+
+	/// When first Tweet within post was created
+	pub created_at: DateTime<Utc>,
+
+	/// URL fragment of Tweet within post, defined for all but first Tweet of a thread
+	pub anchor: Option<String>,
+
+	/// Subdirectory of output directory post is written within, eg. `--community-directory`
+	pub directory: Option<String>,
+}
+
+/// Data gathered before any posts are built
+pub struct Context {
+	/// Archive that media files may be copied from
+	pub archive: Option<Archive>,
+
+	/// Archive media file names grouped by Tweet ID
+	pub media: MediaIndex,
+
+	/// Directory where MarkDown files will be written
+	pub output_directory: path::PathBuf,
+
+	/// Every archived Tweet ID mapped to the post it will be published within
+	pub posts: HashMap<String, LocalPost>,
+
+	/// Every archived Tweet mapped by ID, eg. to render quoted Tweets
+	pub tweets: HashMap<String, Tweet>,
+
+	/// Earlier versions of edited Tweets, oldest first, mapped by ID of latest version
+	pub edits: HashMap<String, Vec<Tweet>>,
+
+	/// Subdirectory of output directory that post currently being built is written within
+	pub directory: Option<String>,
+
+	/// Account that requested archive, from manifest `userInfo`
+	pub user_info: Option<UserInfo>,
+
+	/// When, and how, archive was generated, from manifest `archiveInfo`
+	pub archive_info: Option<ArchiveInfo>,
+
+	/// Every handle account was known by, eg. from `data/screen-name-change.js`
+	pub screen_names: Vec<String>,
+}
+
+impl Context {
+	/// Index media under archive `media_directory` only if `--media-directory` was requested
+	pub fn new(
+		archive: Option<Archive>,
+		media_directory: &str,
+		output_directory: &path::Path,
+		args: &Args,
+	) -> Self {
+		let media = match (&archive, &args.media_directory) {
+			(Some(archive), Some(_)) => MediaIndex::new(archive, media_directory, args),
+			_ => MediaIndex::default(),
+		};
+
+		Self {
+			archive,
+			media,
+			output_directory: output_directory.to_path_buf(),
+			posts: HashMap::new(),
+			tweets: HashMap::new(),
+			edits: HashMap::new(),
+			directory: None,
+			user_info: None,
+			archive_info: None,
+			screen_names: vec![],
+		}
+	}
+
+	/// Record account and archive details of manifest, eg. for `--provenance` FrontMatter
+	pub fn index_manifest(&mut self, data_manifest: &Manifest) {
+		self.user_info = Some(data_manifest.user_info.clone());
+		self.archive_info = Some(data_manifest.archive_info.clone());
+	}
+
+	/// Index media under another archive `media_directory`, eg. `data/community_tweet_media`,
+	/// only if `--media-directory` was requested
+	pub fn index_media(&mut self, media_directory: &str, args: &Args) {
+		if let (Some(archive), Some(_)) = (&self.archive, &args.media_directory) {
+			self.media.extend(archive, media_directory, args);
+		}
+	}
+
+	/// Directory that post currently being built is written within
+	pub fn post_directory(&self) -> path::PathBuf {
+		match &self.directory {
+			Some(directory) => self.output_directory.join(directory),
+			None => self.output_directory.clone(),
+		}
+	}
+
+	/// Record which post each Tweet of `groups` will be published within, first Tweet of each
+	/// group names the post
+	pub fn index_posts(&mut self, groups: &[Vec<&Tweet>], args: &Args) {
+		for group in groups {
+			let root = group[0];
+			let local_post = LocalPost {
+				root_id: root.id_str.clone(),
+				file_name: post_build::file_name(root, args),
+				created_at: root.created_at,
+				anchor: None,
+				directory: post_build::directory(root, args),
+			};
+
+			for tweet in group {
+				let anchor = match tweet.id_str == root.id_str {
+					true => None,
+					false => Some(tweet.id_str.clone()),
+				};
+
+				self.posts.insert(
+					tweet.id_str.clone(),
+					LocalPost {
+						anchor,
+						..local_post.clone()
+					},
+				);
+				self.tweets.insert(tweet.id_str.clone(), (*tweet).clone());
+			}
+		}
+
+		if args.verbose {
+			eprintln!(
+				"context::index_posts -> Tweets indexed: {}",
+				self.posts.len()
+			);
+		}
+	}
+
+	/// Record earlier versions of edited Tweets, so links to them resolve to post of latest
+	/// version, must be called after `index_posts`
+	pub fn index_edits(&mut self, edits: &HashMap<String, Vec<&Tweet>>, args: &Args) {
+		for (id_str, versions) in edits {
+			let local_post = self.posts.get(id_str).cloned();
+
+			for version in versions {
+				if let Some(local_post) = &local_post {
+					self.posts
+						.insert(version.id_str.clone(), local_post.clone());
+				}
+				self.tweets
+					.insert(version.id_str.clone(), (*version).clone());
+			}
+
+			self.edits.insert(
+				id_str.clone(),
+				versions.iter().map(|version| (*version).clone()).collect(),
+			);
+		}
+
+		if args.verbose {
+			eprintln!(
+				"context::index_edits -> Edited Tweets: {}",
+				self.edits.len()
+			);
+		}
+	}
+
+	/// Link to post that archived Tweet with `id` will be published within
+	///
+	/// Uses `--post-permalink` when defined, otherwise post file name relative to post currently
+	/// being built.  Tweets that do not start their post have their anchor appended as URL
+	/// fragment.
+	pub fn local_url(&self, id: &str, args: &Args) -> Option<String> {
+		let local_post = self.posts.get(id)?;
+
+		let mut url = match &args.post_permalink {
+			Some(post_permalink) => {
+				let format = post_permalink.replace("{id}", &local_post.root_id);
+				local_post.created_at.format(&format).to_string()
+			}
+			None if self.directory == local_post.directory => local_post.file_name.clone(),
+			None => {
+				let mut parts: Vec<&str> = vec![];
+				if let Some(directory) = &self.directory {
+					directory
+						.split('/')
+						.filter(|part| !part.is_empty())
+						.for_each(|_| parts.push(".."));
+				}
+				if let Some(directory) = &local_post.directory {
+					parts.push(directory.trim_matches('/'));
+				}
+				parts.push(&local_post.file_name);
+				parts.join("/")
+			}
+		};
+
+		if let Some(anchor) = &local_post.anchor {
+			url.push('#');
+			url.push_str(anchor);
+		}
+
+		Some(url)
+	}
+
+	/// Link for mention of `screen_name` when it is a current, or former, handle of account
+	///
+	/// Uses `--profile-url` when defined, otherwise `--profile-page` relative to post currently
+	/// being built
+	pub fn self_mention_url(&self, screen_name: &str, args: &Args) -> Option<String> {
+		let is_self = self
+			.user_info
+			.iter()
+			.map(|user_info| &user_info.user_name)
+			.chain(self.screen_names.iter())
+			.any(|name| name.eq_ignore_ascii_case(screen_name));
+		if !is_self {
+			return None;
+		}
+
+		if let Some(profile_url) = &args.profile_url {
+			return Some(profile_url.clone());
+		}
+
+		let profile_page_path = path::Path::new(args.profile_page.as_ref()?);
+		let file_name = profile_page_path.file_name()?.to_string_lossy();
+		let directory = profile_page_path.parent().unwrap_or(path::Path::new(""));
+
+		match media::relative_path(&self.post_directory(), directory) {
+			prefix if prefix.is_empty() => Some(file_name.to_string()),
+			prefix => Some(format!("{prefix}/{file_name}")),
+		}
+	}
+
+	/// Archive file name of `media` attached to Tweet with `tweet_id`, if it will be copied
+	pub fn media_file(&self, tweet_id: &str, media: &TweetMedia, args: &Args) -> Option<&str> {
+		args.media_directory.as_ref()?;
+		self.media.find(tweet_id, media)
+	}
+
+	/// Archive file name of poster frame for `video` or `animated_gif` media, if it will be copied
+	pub fn media_poster_file(
+		&self,
+		tweet_id: &str,
+		media: &TweetMedia,
+		args: &Args,
+	) -> Option<&str> {
+		args.media_directory.as_ref()?;
+		self.media.find_remote(tweet_id, &media.media_url_https)
+	}
+
+	/// All archive file names for `media` attached to Tweet with `tweet_id` that will be copied
+	pub fn media_files(&self, tweet_id: &str, media: &TweetMedia, args: &Args) -> Vec<&str> {
+		let mut result: Vec<&str> = vec![];
+
+		if let Some(file_name) = self.media_file(tweet_id, media, args) {
+			result.push(file_name);
+		}
+
+		if media.is_video() {
+			if let Some(file_name) = self.media_poster_file(tweet_id, media, args) {
+				result.push(file_name);
+			}
+		}
+
+		result
+	}
+
+	/// URL path that posts should use to embed `media` attached to Tweet with `tweet_id`
+	pub fn media_url(&self, tweet_id: &str, media: &TweetMedia, args: &Args) -> Option<String> {
+		let file_name = self.media_file(tweet_id, media, args)?;
+		Some(media::url(file_name, &self.post_directory(), args))
+	}
+
+	/// URL that posts should use for embedding `media`, falls back to highest bitrate remote MP4
+	/// for `video` and `animated_gif` media not found within archive
+	pub fn media_src(&self, tweet_id: &str, media: &TweetMedia, args: &Args) -> Option<String> {
+		args.media_directory.as_ref()?;
+
+		if let Some(url) = self.media_url(tweet_id, media, args) {
+			return Some(url);
+		}
+
+		if media.is_video() {
+			return media.best_variant().map(|variant| variant.url.clone());
+		}
+
+		None
+	}
+
+	/// URL of poster frame for `video` and `animated_gif` media, local copy if available
+	pub fn media_poster(&self, tweet_id: &str, media: &TweetMedia, args: &Args) -> String {
+		match self.media_poster_file(tweet_id, media, args) {
+			Some(file_name) => media::url(file_name, &self.post_directory(), args),
+			None => media.media_url_https.clone(),
+		}
+	}
+}
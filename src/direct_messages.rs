@@ -0,0 +1,461 @@
+#!/usr/bin/env rust
+
+//! Write Direct Message conversations, from archive `data/direct-messages.js` and
+//! `data/direct-messages-group.js`, as one MarkDown transcript per conversation
+//!
+//! Direct Messages are private, so transcripts are only written when `--direct-messages-directory`
+//! is set, and never within `--output-directory` or `--media-directory`.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::{io, path};
+use twitter_archive::structs::direct_message::{MessageCreateReaction, MessageCreateUrl};
+use twitter_archive::structs::manifest::Manifest;
+use twitter_archive::structs::{direct_messages, direct_messages_group};
+
+use crate::archive::Archive;
+use crate::arguments::Args;
+use crate::context::Context;
+use crate::media::{self, MediaIndex};
+use crate::output;
+use crate::post_build;
+
+/// Single conversation, either one-to-one or group, with events oldest first
+struct Conversation<'a> {
+	/// ID of conversation, eg. `111111111-222222222` for one-to-one conversations
+	id: &'a str,
+
+	/// Whether conversation was listed by `data/direct-messages-group.js`
+	group: bool,
+
+	/// Messages, and participant changes, of conversation
+	events: Vec<Event<'a>>,
+}
+
+/// Entry of a conversation transcript
+enum Event<'a> {
+	/// Message sent by a participant
+	Message(Message<'a>),
+
+	/// Participants left a group conversation
+	Leave {
+		/// When participants left
+		created_at: DateTime<Utc>,
+
+		/// IDs of accounts that left
+		user_ids: &'a [String],
+	},
+
+	/// Participants were added to a group conversation
+	Join {
+		/// When participants were added
+		created_at: DateTime<Utc>,
+
+		/// ID of account that added participants
+		initiating_user_id: &'a str,
+	},
+}
+
+impl Event<'_> {
+	/// When event happened
+	fn created_at(&self) -> DateTime<Utc> {
+		match self {
+			Event::Message(message) => message.created_at,
+			Event::Leave { created_at, .. } | Event::Join { created_at, .. } => *created_at,
+		}
+	}
+}
+
+/// Fields shared by `messageCreate` events of one-to-one and group conversations
+struct Message<'a> {
+	/// ID of message, also prefixes names of its media files within archive
+	id: &'a str,
+
+	/// ID of account that sent message
+	sender_id: &'a str,
+
+	/// When message was sent
+	created_at: DateTime<Utc>,
+
+	/// Message text with shortened `https://t.co/...` links
+	text: &'a str,
+
+	/// Shortened links found within `text`
+	urls: &'a [MessageCreateUrl],
+
+	/// Remote URLs of attached media
+	media_urls: &'a [String],
+
+	/// Reactions of participants to message
+	reactions: &'a [MessageCreateReaction],
+}
+
+/// Write a transcript per conversation into `--direct-messages-directory`, if defined
+pub fn to_markdown(
+	archive: &Archive,
+	data_manifest: &Manifest,
+	context: &Context,
+	args: &Args,
+) -> io::Result<()> {
+	let Some(direct_messages_directory) = &args.direct_messages_directory else {
+		return Ok(());
+	};
+
+	let direct_messages_directory_path = path::Path::new(direct_messages_directory);
+	if let Some(public_directory) = output::public_directory_of(
+		direct_messages_directory_path,
+		&context.output_directory,
+		args,
+	) {
+		eprintln!(
+			"direct_messages::to_markdown -> Refusing to write Direct Messages within -> {}",
+			public_directory.display()
+		);
+		return Ok(());
+	}
+
+	let data_types = &data_manifest.data_types;
+	let data_direct_messages: Vec<direct_messages::DmConversationObject> =
+		archive.read_data(&data_types.direct_messages.files, args)?;
+	let data_direct_messages_group: Vec<direct_messages_group::DmConversationObject> =
+		archive.read_data(&data_types.direct_messages_group.files, args)?;
+
+	let mut conversations: Vec<Conversation> = vec![];
+	for object in &data_direct_messages {
+		let conversation = &object.dm_conversation;
+		conversations.push(Conversation {
+			id: &conversation.conversation_id,
+			group: false,
+			events: conversation
+				.messages
+				.iter()
+				.map(|message| {
+					let message = &message.message_create;
+					Event::Message(Message {
+						id: &message.id,
+						sender_id: &message.sender_id,
+						created_at: message.created_at,
+						text: &message.text,
+						urls: &message.urls,
+						media_urls: &message.media_urls,
+						reactions: &message.reactions,
+					})
+				})
+				.collect(),
+		});
+	}
+
+	for object in &data_direct_messages_group {
+		let conversation = &object.dm_conversation;
+		conversations.push(Conversation {
+			id: &conversation.conversation_id,
+			group: true,
+			events: conversation
+				.messages
+				.iter()
+				.map(|message| match message {
+					direct_messages_group::Message::MessageCreate(message) => {
+						Event::Message(Message {
+							id: &message.id,
+							sender_id: &message.sender_id,
+							created_at: message.created_at,
+							text: &message.text,
+							urls: &message.urls,
+							media_urls: &message.media_urls,
+							reactions: &message.reactions,
+						})
+					}
+					direct_messages_group::Message::ParticipantsLeave(leave) => Event::Leave {
+						created_at: leave.created_at,
+						user_ids: &leave.user_ids,
+					},
+					direct_messages_group::Message::JoinConversation(join) => Event::Join {
+						created_at: join.created_at,
+						initiating_user_id: &join.initiating_user_id,
+					},
+				})
+				.collect(),
+		});
+	}
+
+	if args.verbose {
+		eprintln!(
+			"direct_messages::to_markdown -> Conversations found: {}",
+			conversations.len()
+		);
+	}
+
+	// Media is kept beside transcripts instead of within `--media-directory`
+	let args = Args {
+		output_directory: Some(direct_messages_directory.clone()),
+		media_directory: Some(
+			direct_messages_directory_path
+				.join("media")
+				.display()
+				.to_string(),
+		),
+		media_url_prefix: None,
+		..args.clone()
+	};
+
+	output::create_directory(direct_messages_directory_path, &args)?;
+	output::create_directory(&direct_messages_directory_path.join("media"), &args)?;
+
+	let mut media_index = MediaIndex::new(
+		archive,
+		&data_types.direct_messages_media.media_directory,
+		&args,
+	);
+	media_index.extend(
+		archive,
+		&data_types.direct_messages_group_media.media_directory,
+		&args,
+	);
+
+	let handles = handles(data_manifest, context);
+
+	for mut conversation in conversations {
+		conversation.events.sort_by_key(Event::created_at);
+
+		for event in &conversation.events {
+			if let Event::Message(message) = event {
+				for media_url in message.media_urls {
+					if let Some(file_name) = media_index.find_remote(message.id, media_url) {
+						media::copy(archive, file_name, &args)?;
+					}
+				}
+			}
+		}
+
+		let file_name = format!("{}.md", conversation.id);
+		output::write_markdown(
+			&direct_messages_directory_path.join(file_name),
+			&transcript(&conversation, &media_index, &handles, &args),
+			&args,
+		)?;
+	}
+
+	Ok(())
+}
+
+/// Map account IDs to screen names, from manifest `userInfo` and accounts mentioned or replied
+/// to by archived Tweets, because Direct Messages only list account IDs
+fn handles(data_manifest: &Manifest, context: &Context) -> HashMap<String, String> {
+	let mut result: HashMap<String, String> = HashMap::new();
+
+	for tweet in context.tweets.values() {
+		for user_mention in &tweet.entities.user_mentions {
+			if !user_mention.id_str.is_empty() {
+				result.insert(
+					user_mention.id_str.clone(),
+					user_mention.screen_name.clone(),
+				);
+			}
+		}
+
+		if let (Some(id_str), Some(screen_name)) = (
+			&tweet.in_reply_to_user_id_str,
+			&tweet.in_reply_to_screen_name,
+		) {
+			result.insert(id_str.clone(), screen_name.clone());
+		}
+	}
+
+	let user_info = &data_manifest.user_info;
+	result.insert(user_info.account_id.clone(), user_info.user_name.clone());
+
+	result
+}
+
+/// Build MarkDown, with FrontMatter, transcript of `conversation`
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ---
+/// conversation: "111111111-222222222"
+/// date: "2020-01-20 21:42:09 +0000"
+/// participants:
+///   - "@S0AndS0"
+///   - "222222222"
+/// ---
+///
+/// **[@S0AndS0](https://twitter.com/S0AndS0)** -- 2020-01-20 21:42:09 +0000
+///
+/// Salutations!
+///
+/// > Reactions: ❤️ [222222222](https://twitter.com/i/user/222222222)
+/// ```
+fn transcript(
+	conversation: &Conversation,
+	media_index: &MediaIndex,
+	handles: &HashMap<String, String>,
+	args: &Args,
+) -> String {
+	let mut participants: Vec<&str> = vec![];
+	for event in &conversation.events {
+		if let Event::Message(message) = event {
+			if !participants.contains(&message.sender_id) {
+				participants.push(message.sender_id);
+			}
+		}
+	}
+
+	let mut lines: Vec<String> = vec![];
+	lines.push(format!("conversation: {:?}", conversation.id));
+	if let Some(event) = conversation.events.first() {
+		lines.push(format!(
+			"date: {:?}",
+			event
+				.created_at()
+				.format(&args.post_date_format)
+				.to_string()
+		));
+	}
+	if conversation.group {
+		lines.push("group: true".into());
+	}
+	lines.push("participants:".into());
+	for sender_id in &participants {
+		let participant = match handles.get(*sender_id) {
+			Some(screen_name) => format!("@{screen_name}"),
+			None => sender_id.to_string(),
+		};
+		lines.push(format!("  - {:?}", participant));
+	}
+
+	let output_directory = path::PathBuf::from(args.output_directory.clone().unwrap_or_default());
+	let body = conversation
+		.events
+		.iter()
+		.map(|event| match event {
+			Event::Message(message) => {
+				content_message(message, media_index, handles, &output_directory, args)
+			}
+			Event::Leave {
+				created_at,
+				user_ids,
+			} => format!(
+				"*{} left -- {}*",
+				user_ids
+					.iter()
+					.map(|user_id| link_account(user_id, handles, args))
+					.collect::<Vec<String>>()
+					.join(", "),
+				created_at.format(&args.post_date_format)
+			),
+			Event::Join {
+				created_at,
+				initiating_user_id,
+			} => format!(
+				"*{} added participants -- {}*",
+				link_account(initiating_user_id, handles, args),
+				created_at.format(&args.post_date_format)
+			),
+		})
+		.collect::<Vec<String>>()
+		.join("\n\n");
+
+	format!("---\n{}\n---\n\n{}\n", lines.join("\n"), body)
+}
+
+/// Sender, time, text with expanded links, embedded media, and reactions of a message
+fn content_message(
+	message: &Message,
+	media_index: &MediaIndex,
+	handles: &HashMap<String, String>,
+	output_directory: &path::Path,
+	args: &Args,
+) -> String {
+	let mut parts: Vec<String> = vec![];
+
+	parts.push(format!(
+		"**{}** -- {}",
+		link_account(message.sender_id, handles, args),
+		message.created_at.format(&args.post_date_format)
+	));
+
+	// Attachments are also linked by a shortened URL, which embedded media replaces
+	let mut text = message.text.to_string();
+	for url in message.urls {
+		let is_attachment =
+			!message.media_urls.is_empty() && url.expanded.contains("/messages/media/");
+		let replacement = match is_attachment {
+			true => String::new(),
+			false => format!("[{}]({})", url.display, url.expanded),
+		};
+		text = text.replace(&url.url, &replacement);
+	}
+
+	for media_url in message.media_urls {
+		match media_index.find_remote(message.id, media_url) {
+			Some(file_name) => {
+				let src = media::url(file_name, output_directory, args);
+				match media::is_video_file(file_name) {
+					true => parts.push(format!(
+						"<video src=\"{src}\" controls preload=\"metadata\"></video>"
+					)),
+					false => parts.push(format!("![]({src})")),
+				}
+			}
+			None => parts.push(format!(
+				"[{}]({})",
+				media::url_base_name(media_url),
+				media_url
+			)),
+		}
+	}
+
+	let text = text.trim();
+	if !text.is_empty() {
+		parts.insert(1, text.to_string());
+	}
+
+	if !message.reactions.is_empty() {
+		let reactions = message
+			.reactions
+			.iter()
+			.map(|reaction| {
+				format!(
+					"{} {}",
+					reaction_emoji(&reaction.reaction_key),
+					link_account(&reaction.sender_id, handles, args)
+				)
+			})
+			.collect::<Vec<String>>()
+			.join(", ");
+		parts.push(post_build::markdown_blockquote(&format!(
+			"Reactions: {reactions}"
+		)));
+	}
+
+	parts.join("\n\n")
+}
+
+/// Link to account with `user_id`, named by screen name when known
+fn link_account(user_id: &str, handles: &HashMap<String, String>, args: &Args) -> String {
+	match handles.get(user_id) {
+		Some(screen_name) => format!(
+			"[@{screen_name}]({})",
+			post_build::twitter_url_account(screen_name, args)
+		),
+		None => format!(
+			"[{user_id}]({})",
+			post_build::twitter_url_user_id(user_id, args)
+		),
+	}
+}
+
+/// Emoji for `reactionKey` values found within archives, or the key itself when unknown
+fn reaction_emoji(reaction_key: &str) -> &str {
+	match reaction_key {
+		"like" => "❤️",
+		"funny" => "😂",
+		"surprised" => "😲",
+		"sad" => "😢",
+		"agree" => "👍",
+		"disagree" => "👎",
+		"excited" => "🔥",
+		other => other,
+	}
+}
@@ -0,0 +1,235 @@
+#!/usr/bin/env rust
+
+//! Write every data type listed by archive manifest, eg. `data/block.js` or `data/mute.js`, as
+//! untyped data without requiring a dedicated struct per data type
+//!
+//! Data types include private data, such as IP addresses and email, so files are only written
+//! when `--dump-directory` is set, and never within `--output-directory` or `--media-directory`.
+
+use std::{io, path};
+use twitter_archive::structs::manifest;
+
+use crate::archive::Archive;
+use crate::arguments::{Args, DumpFormat};
+use crate::output;
+
+/// Write one file per manifest data type into `--dump-directory`, if defined
+///
+/// `output_directory_path` is `None` when only data types are written, without posts.
+///
+/// Manifest is read untyped, so data types `twitter_archive` does not know of are written too,
+/// while data types that list no files, or cannot be parsed, are skipped with a warning
+pub fn to_files(
+	archive: &Archive,
+	output_directory_path: Option<&path::Path>,
+	args: &Args,
+) -> io::Result<()> {
+	let Some(dump_directory) = &args.dump_directory else {
+		return Ok(());
+	};
+
+	// Without `output_directory_path`, nothing but data types is written, so none are published
+	let dump_directory_path = path::Path::new(dump_directory);
+	if let Some(public_directory) = output_directory_path.and_then(|output_directory_path| {
+		output::public_directory_of(dump_directory_path, output_directory_path, args)
+	}) {
+		eprintln!(
+			"dump::to_files -> Refusing to write data types within -> {}",
+			public_directory.display()
+		);
+		return Ok(());
+	}
+	output::create_directory(dump_directory_path, args)?;
+
+	let data_manifest = match archive.manifest_value() {
+		Ok(data_manifest) => data_manifest,
+		Err(error) => {
+			eprintln!("dump::to_files -> Skipping every data type -> {error}");
+			return Ok(());
+		}
+	};
+	let Some(data_types) = data_manifest
+		.get("dataTypes")
+		.and_then(serde_json::Value::as_object)
+	else {
+		eprintln!(
+			"dump::to_files -> Skipping every data type -> No dataTypes within data/manifest.js"
+		);
+		return Ok(());
+	};
+
+	for (name, data_type) in data_types {
+		let files = match data_type
+			.get("files")
+			.cloned()
+			.map(serde_json::from_value::<Vec<manifest::File>>)
+		{
+			Some(Ok(files)) => files,
+			Some(Err(error)) => {
+				eprintln!("dump::to_files -> Skipping {name} -> Unable to parse files -> {error}");
+				continue;
+			}
+			None => vec![],
+		};
+		if files.is_empty() {
+			continue;
+		}
+
+		let entries: Vec<serde_json::Value> = match archive.read_data(&files, args) {
+			Ok(entries) => entries,
+			Err(error) => {
+				eprintln!("dump::to_files -> Skipping {name} -> {error}");
+				continue;
+			}
+		};
+
+		if args.verbose {
+			eprintln!(
+				"dump::to_files -> {name} -> Entries found: {}",
+				entries.len()
+			);
+		}
+
+		let (file_name, content) = match args.dump_format {
+			DumpFormat::Markdown => (format!("{name}.md"), markdown(name, &files, &entries)),
+			DumpFormat::Json => (
+				format!("{name}.json"),
+				format!(
+					"{}\n",
+					serde_json::to_string_pretty(&entries).expect("Unable to serialize as JSON")
+				),
+			),
+		};
+
+		output::write_data(&dump_directory_path.join(file_name), &content, args)?;
+	}
+
+	Ok(())
+}
+
+/// Build MarkDown table with a row per entry and a column per flattened, `.` separated, key
+///
+/// When every entry wraps its data within the same single key, eg. `{ "block": { ... } }`, that
+/// key is left out of column names.
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// # block
+///
+/// Source: `data/block.js`
+///
+/// | accountId | userLink |
+/// | --- | --- |
+/// | 123 | https://twitter.com/intent/user?user_id=123 |
+/// ```
+fn markdown(name: &str, files: &[manifest::File], entries: &[serde_json::Value]) -> String {
+	let mut blocks: Vec<String> = vec![];
+
+	blocks.push(format!("# {name}"));
+	blocks.push(format!(
+		"Source: {}",
+		files
+			.iter()
+			.map(|file| format!("`{}`", file.file_name))
+			.collect::<Vec<String>>()
+			.join(", ")
+	));
+
+	let wrapper = wrapper_key(entries);
+	let rows = entries
+		.iter()
+		.map(|entry| {
+			let mut cells: Vec<(String, String)> = vec![];
+			match (wrapper, entry) {
+				(Some(key), serde_json::Value::Object(object)) => {
+					flatten("", &object[key], &mut cells)
+				}
+				_ => flatten("", entry, &mut cells),
+			}
+			cells
+		})
+		.collect::<Vec<Vec<(String, String)>>>();
+
+	let mut columns: Vec<&str> = vec![];
+	for row in &rows {
+		for (column, _) in row {
+			if !columns.contains(&column.as_str()) {
+				columns.push(column);
+			}
+		}
+	}
+
+	if rows.is_empty() || columns.is_empty() {
+		blocks.push("No entries found within archive".into());
+		return format!("{}\n", blocks.join("\n\n"));
+	}
+
+	let mut table: Vec<String> = vec![];
+	table.push(format!(
+		"| {} |",
+		columns
+			.iter()
+			.map(|column| markdown_cell(column))
+			.collect::<Vec<String>>()
+			.join(" | ")
+	));
+	table.push(format!("|{}", " --- |".repeat(columns.len())));
+	for row in &rows {
+		let cells = columns
+			.iter()
+			.map(|column| {
+				row.iter()
+					.find(|(key, _)| key == column)
+					.map(|(_, value)| markdown_cell(value))
+					.unwrap_or_default()
+			})
+			.collect::<Vec<String>>();
+		table.push(format!("| {} |", cells.join(" | ")));
+	}
+	blocks.push(table.join("\n"));
+
+	format!("{}\n", blocks.join("\n\n"))
+}
+
+/// Single key that every entry is an object of, eg. `block` for `data/block.js`
+fn wrapper_key(entries: &[serde_json::Value]) -> Option<&str> {
+	let mut result: Option<&str> = None;
+
+	for entry in entries {
+		let object = entry.as_object().filter(|object| object.len() == 1)?;
+		let (key, value) = object.iter().next()?;
+		if !value.is_object() || result.is_some_and(|result| result != key) {
+			return None;
+		}
+		result = Some(key);
+	}
+
+	result
+}
+
+/// Collect `.` separated key paths and text of every scalar within `value`, arrays are kept as
+/// compact JSON
+fn flatten(prefix: &str, value: &serde_json::Value, cells: &mut Vec<(String, String)>) {
+	match value {
+		serde_json::Value::Object(object) => {
+			for (key, value) in object {
+				let key = match prefix.is_empty() {
+					true => key.clone(),
+					false => format!("{prefix}.{key}"),
+				};
+				flatten(&key, value, cells);
+			}
+		}
+		serde_json::Value::String(text) => cells.push((prefix.to_string(), text.clone())),
+		serde_json::Value::Null => cells.push((prefix.to_string(), String::new())),
+		_ => cells.push((prefix.to_string(), value.to_string())),
+	}
+}
+
+/// Escape text so it stays within a single MarkDown table cell
+fn markdown_cell(text: &str) -> String {
+	text.replace('|', "\\|")
+		.replace("\r\n", "<br>")
+		.replace(['\n', '\r'], "<br>")
+}
@@ -0,0 +1,58 @@
+#!/usr/bin/env rust
+
+//! Collapse versions of edited Tweets, which archives list as separate Tweets that share the same
+//! `edit_info.initial.editTweetIds` list
+
+use std::collections::HashMap;
+
+use crate::arguments::Args;
+use crate::structs::Tweet;
+
+/// Keep only the latest archived version of each edited Tweet
+///
+/// Returns kept Tweets, in their original order, and earlier versions, oldest first, mapped by ID
+/// of the version that was kept
+pub fn collapse<'a>(
+	tweets: Vec<&'a Tweet>,
+	args: &Args,
+) -> (Vec<&'a Tweet>, HashMap<String, Vec<&'a Tweet>>) {
+	let by_id = tweets
+		.iter()
+		.map(|tweet| (tweet.id_str.as_str(), *tweet))
+		.collect::<HashMap<&str, &Tweet>>();
+
+	let mut kept: Vec<&Tweet> = vec![];
+	let mut earlier: HashMap<String, Vec<&Tweet>> = HashMap::new();
+	for tweet in &tweets {
+		let edit_tweet_ids = tweet.edit_tweet_ids();
+		if edit_tweet_ids.len() < 2 || !edit_tweet_ids.contains(&tweet.id_str) {
+			kept.push(tweet);
+			continue;
+		}
+
+		let versions = edit_tweet_ids
+			.iter()
+			.filter_map(|id| by_id.get(id.as_str()).copied())
+			.collect::<Vec<&Tweet>>();
+
+		let latest = versions.last().copied().unwrap_or(tweet);
+		if latest.id_str != tweet.id_str {
+			if args.verbose {
+				eprintln!(
+					"edits::collapse -> {} -> Superseded by edit -> {}",
+					tweet.id_str, latest.id_str
+				);
+			}
+			continue;
+		}
+
+		kept.push(tweet);
+
+		let previous = versions[..versions.len().saturating_sub(1)].to_vec();
+		if !previous.is_empty() {
+			earlier.insert(tweet.id_str.clone(), previous);
+		}
+	}
+
+	(kept, earlier)
+}
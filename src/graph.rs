@@ -0,0 +1,300 @@
+#!/usr/bin/env rust
+
+//! Write followers and following, from archive `data/follower.js` and `data/following.js`, as
+//! CSV, JSON, and GraphML
+//!
+//! Archives only list account IDs, and `userLink`s, of followers and following, so screen names
+//! and interaction counts are joined from `user_mentions` of archived Tweets.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::{io, path};
+use twitter_archive::structs::follower::FollowerObject;
+use twitter_archive::structs::following::FollowingObject;
+use twitter_archive::structs::manifest::Manifest;
+
+use crate::archive::Archive;
+use crate::arguments::Args;
+use crate::output;
+use crate::structs::TweetObject;
+
+/// Whole social graph of account that requested archive
+#[derive(Serialize, Debug)]
+struct Graph<'a> {
+	/// ID of account that requested archive
+	account_id: &'a str,
+
+	/// Handle of account that requested archive
+	screen_name: &'a str,
+
+	/// Every follower and followed account, most mentioned first
+	accounts: Vec<Edge>,
+}
+
+/// Relationship between account that requested archive and one follower, or followed, account
+#[derive(Serialize, Debug, Default)]
+struct Edge {
+	/// ID of other account
+	account_id: String,
+
+	/// Handle of other account, only known when archived Tweets mention it
+	screen_name: Option<String>,
+
+	/// Link to other account by ID, eg. `https://twitter.com/intent/user?user_id=123`
+	user_link: String,
+
+	/// Whether other account follows account that requested archive
+	follower: bool,
+
+	/// Whether account that requested archive follows other account
+	following: bool,
+
+	/// Whether both accounts follow each other
+	mutual: bool,
+
+	/// Number of archived Tweets mentioning other account
+	mentions: usize,
+
+	/// When other account was last mentioned
+	last_mentioned_at: Option<DateTime<Utc>>,
+}
+
+/// Write `graph.csv`, `graph.json`, and `graph.graphml` into `--graph-directory`, if defined
+pub fn to_files(
+	archive: &Archive,
+	data_manifest: &Manifest,
+	data_tweets: &[TweetObject],
+	args: &Args,
+) -> io::Result<()> {
+	let Some(graph_directory) = &args.graph_directory else {
+		return Ok(());
+	};
+
+	let data_types = &data_manifest.data_types;
+	let data_follower: Vec<FollowerObject> = archive.read_data(&data_types.follower.files, args)?;
+	let data_following: Vec<FollowingObject> =
+		archive.read_data(&data_types.following.files, args)?;
+
+	let mut edges: BTreeMap<String, Edge> = BTreeMap::new();
+	for follow in data_follower.iter().map(|object| &object.follower) {
+		let edge = edges.entry(follow.account_id.clone()).or_default();
+		edge.user_link.clone_from(&follow.user_link);
+		edge.follower = true;
+	}
+	for follow in data_following.iter().map(|object| &object.following) {
+		let edge = edges.entry(follow.account_id.clone()).or_default();
+		edge.user_link.clone_from(&follow.user_link);
+		edge.following = true;
+	}
+
+	for tweet in data_tweets.iter().map(|object| &object.tweet) {
+		let mut seen: Vec<&str> = vec![];
+		for mention in &tweet.entities.user_mentions {
+			if seen.contains(&mention.id_str.as_str()) {
+				continue;
+			}
+			seen.push(&mention.id_str);
+
+			if let Some(edge) = edges.get_mut(&mention.id_str) {
+				edge.screen_name = Some(mention.screen_name.clone());
+				edge.mentions += 1;
+				if edge.last_mentioned_at < Some(tweet.created_at) {
+					edge.last_mentioned_at = Some(tweet.created_at);
+				}
+			}
+		}
+	}
+
+	let mut accounts = edges
+		.into_iter()
+		.map(|(account_id, edge)| Edge {
+			mutual: edge.follower && edge.following,
+			account_id,
+			..edge
+		})
+		.collect::<Vec<Edge>>();
+	accounts.sort_by_key(|edge| Reverse(edge.mentions));
+
+	if args.verbose {
+		eprintln!(
+			"graph::to_files -> Followers: {} -> Following: {} -> Accounts: {}",
+			data_follower.len(),
+			data_following.len(),
+			accounts.len()
+		);
+	}
+
+	let graph = Graph {
+		account_id: &data_manifest.user_info.account_id,
+		screen_name: &data_manifest.user_info.user_name,
+		accounts,
+	};
+
+	let graph_directory_path = path::Path::new(graph_directory);
+	output::create_directory(graph_directory_path, args)?;
+
+	output::write_data(&graph_directory_path.join("graph.csv"), &csv(&graph), args)?;
+	let json = serde_json::to_string_pretty(&graph).expect("Unable to serialize graph as JSON");
+	output::write_data(
+		&graph_directory_path.join("graph.json"),
+		&format!("{json}\n"),
+		args,
+	)?;
+	output::write_data(
+		&graph_directory_path.join("graph.graphml"),
+		&graphml(&graph),
+		args,
+	)?;
+
+	Ok(())
+}
+
+/// One row per account with header row of column names
+fn csv(graph: &Graph) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(
+		"account_id,screen_name,user_link,follower,following,mutual,mentions,last_mentioned_at"
+			.into(),
+	);
+	for edge in &graph.accounts {
+		lines.push(
+			[
+				csv_field(&edge.account_id),
+				csv_field(edge.screen_name.as_deref().unwrap_or_default()),
+				csv_field(&edge.user_link),
+				edge.follower.to_string(),
+				edge.following.to_string(),
+				edge.mutual.to_string(),
+				edge.mentions.to_string(),
+				edge.last_mentioned_at
+					.map(|date| date.to_rfc3339_opts(SecondsFormat::Secs, true))
+					.unwrap_or_default(),
+			]
+			.join(","),
+		);
+	}
+
+	format!("{}\n", lines.join("\n"))
+}
+
+/// Quote CSV `value` when it contains separators, quotes, or line breaks
+fn csv_field(value: &str) -> String {
+	match value.contains([',', '"', '\n', '\r']) {
+		true => format!("\"{}\"", value.replace('"', "\"\"")),
+		false => value.to_string(),
+	}
+}
+
+/// Directed graph with an edge from follower to followed account, so mutual follows have an edge
+/// each way
+///
+/// ## Example output GraphML
+///
+/// ```xml
+/// <?xml version="1.0" encoding="UTF-8"?>
+/// <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+///   <key id="screen_name" for="node" attr.name="screen_name" attr.type="string"/>
+///   <key id="user_link" for="node" attr.name="user_link" attr.type="string"/>
+///   <key id="mentions" for="edge" attr.name="mentions" attr.type="int"/>
+///   <key id="mutual" for="edge" attr.name="mutual" attr.type="boolean"/>
+///   <graph id="follows" edgedefault="directed">
+///     <node id="111111111">
+///       <data key="screen_name">S0_And_S0</data>
+///     </node>
+///     <node id="222222222">
+///       <data key="screen_name">Alice</data>
+///       <data key="user_link">https://twitter.com/intent/user?user_id=222222222</data>
+///     </node>
+///     <edge source="111111111" target="222222222">
+///       <data key="mentions">3</data>
+///       <data key="mutual">false</data>
+///     </edge>
+///   </graph>
+/// </graphml>
+/// ```
+fn graphml(graph: &Graph) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(r#"<?xml version="1.0" encoding="UTF-8"?>"#.into());
+	lines.push(r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#.into());
+	lines.push(
+		r#"  <key id="screen_name" for="node" attr.name="screen_name" attr.type="string"/>"#.into(),
+	);
+	lines.push(
+		r#"  <key id="user_link" for="node" attr.name="user_link" attr.type="string"/>"#.into(),
+	);
+	lines.push(r#"  <key id="mentions" for="edge" attr.name="mentions" attr.type="int"/>"#.into());
+	lines.push(r#"  <key id="mutual" for="edge" attr.name="mutual" attr.type="boolean"/>"#.into());
+	lines.push(r#"  <graph id="follows" edgedefault="directed">"#.into());
+
+	lines.push(format!(
+		r#"    <node id="{}">"#,
+		xml_escape(graph.account_id)
+	));
+	lines.push(format!(
+		r#"      <data key="screen_name">{}</data>"#,
+		xml_escape(graph.screen_name)
+	));
+	lines.push("    </node>".into());
+
+	for edge in &graph.accounts {
+		lines.push(format!(
+			r#"    <node id="{}">"#,
+			xml_escape(&edge.account_id)
+		));
+		if let Some(screen_name) = &edge.screen_name {
+			lines.push(format!(
+				r#"      <data key="screen_name">{}</data>"#,
+				xml_escape(screen_name)
+			));
+		}
+		lines.push(format!(
+			r#"      <data key="user_link">{}</data>"#,
+			xml_escape(&edge.user_link)
+		));
+		lines.push("    </node>".into());
+	}
+
+	for edge in &graph.accounts {
+		let mut directions: Vec<(&str, &str)> = vec![];
+		if edge.following {
+			directions.push((graph.account_id, &edge.account_id));
+		}
+		if edge.follower {
+			directions.push((&edge.account_id, graph.account_id));
+		}
+
+		for (source, target) in directions {
+			lines.push(format!(
+				r#"    <edge source="{}" target="{}">"#,
+				xml_escape(source),
+				xml_escape(target)
+			));
+			lines.push(format!(
+				r#"      <data key="mentions">{}</data>"#,
+				edge.mentions
+			));
+			lines.push(format!(
+				r#"      <data key="mutual">{}</data>"#,
+				edge.mutual
+			));
+			lines.push("    </edge>".into());
+		}
+	}
+
+	lines.push("  </graph>".into());
+	lines.push("</graphml>".into());
+
+	format!("{}\n", lines.join("\n"))
+}
+
+/// Replace characters that XML reserves with their entities
+fn xml_escape(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
@@ -0,0 +1,198 @@
+#!/usr/bin/env rust
+
+//! Write liked Tweets, from archive `data/like.js`, as a link-blog collection
+//!
+//! Archives do not record when a Tweet was liked, nor when liked Tweets were created, so dates
+//! are approximated from the timestamp encoded within Snowflake Tweet IDs.
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use std::collections::BTreeMap;
+use std::{io, path};
+use twitter_archive::structs::like::{Like, LikeObject};
+use twitter_archive::structs::manifest::Manifest;
+
+use crate::archive::Archive;
+use crate::arguments::{Args, LikesPolicy};
+use crate::output;
+use crate::post_build;
+
+/// Milliseconds between Unix epoch and Twitter epoch that Snowflake IDs count from
+const TWITTER_EPOCH_MILLIS: i64 = 1_288_834_974_657;
+
+/// Lowest Tweet ID generated by Snowflake, earlier IDs are sequential and encode no time
+const FIRST_SNOWFLAKE_ID: u64 = 29_700_859_247;
+
+/// Write likes listed by manifest into `--likes-output-directory` when `--likes` is not `skip`
+pub fn to_markdown(
+	archive: &Archive,
+	data_manifest: &Manifest,
+	output_directory_path: &path::Path,
+	args: &Args,
+) -> io::Result<()> {
+	if args.likes == LikesPolicy::Skip {
+		return Ok(());
+	}
+
+	let data_likes: Vec<LikeObject> =
+		archive.read_data(&data_manifest.data_types.like.files, args)?;
+	if args.verbose {
+		eprintln!("likes::to_markdown -> Likes found: {}", data_likes.len());
+	}
+
+	let likes_output_directory = args
+		.likes_output_directory
+		.clone()
+		.unwrap_or(output::sibling_directory(output_directory_path, "likes"));
+	let likes_output_directory_path = path::Path::new(&likes_output_directory);
+	output::create_directory(likes_output_directory_path, args)?;
+
+	let likes = data_likes
+		.iter()
+		.map(|object| &object.like)
+		.collect::<Vec<&Like>>();
+
+	match args.likes {
+		LikesPolicy::Each => {
+			for like in likes {
+				let file_name = match snowflake_created_at(&like.tweet_id) {
+					Some(date) => format!("{}-{}.md", date.format("%F"), like.tweet_id),
+					None => format!("{}.md", like.tweet_id),
+				};
+
+				output::write_markdown(
+					&likes_output_directory_path.join(file_name),
+					&post(like, args),
+					args,
+				)?;
+			}
+		}
+		LikesPolicy::Month => {
+			let mut months: BTreeMap<Option<(i32, u32)>, Vec<&Like>> = BTreeMap::new();
+			for like in likes {
+				let month =
+					snowflake_created_at(&like.tweet_id).map(|date| (date.year(), date.month()));
+				months.entry(month).or_default().push(like);
+			}
+
+			for (month, mut likes) in months {
+				likes.sort_by_key(|like| like.tweet_id.parse::<u64>().unwrap_or_default());
+
+				let file_name = match month {
+					Some((year, month)) => format!("{year:04}-{month:02}.md"),
+					None => "undated.md".to_string(),
+				};
+
+				// Monthly pages gather every like, so are rewritten as newer archives add likes
+				output::write_data(
+					&likes_output_directory_path.join(file_name),
+					&month_post(month, &likes, args),
+					args,
+				)?;
+			}
+		}
+		LikesPolicy::Skip => {}
+	}
+
+	Ok(())
+}
+
+/// Approximate creation time of Tweet with `id_str`, `None` for IDs that predate Snowflake
+///
+/// ## Example
+///
+/// ```ignore
+/// let date = snowflake_created_at("1697011324369178968").unwrap();
+/// assert_eq!(date.format("%F").to_string(), "2023-08-30");
+/// ```
+pub fn snowflake_created_at(id_str: &str) -> Option<DateTime<Utc>> {
+	let id = id_str.parse::<u64>().ok()?;
+	if id < FIRST_SNOWFLAKE_ID {
+		return None;
+	}
+
+	let millis = i64::try_from(id >> 22).ok()? + TWITTER_EPOCH_MILLIS;
+	Utc.timestamp_millis_opt(millis).single()
+}
+
+/// Build MarkDown, with FrontMatter, for a single like
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ---
+/// layout: like
+/// date: "2023-08-31 01:00:23 +0000"
+/// twitter:
+///   post: https://twitter.com/i/web/status/1697051672621597026
+/// ---
+///
+/// > https://t.co/IaCJlkaweW
+/// >
+/// > -- [twitter.com/i/web/status/1697051672621597026](https://twitter.com/i/web/status/1697051672621597026)
+/// ```
+fn post(like: &Like, args: &Args) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(format!("layout: {}", args.likes_post_layout));
+	if let Some(date) = snowflake_created_at(&like.tweet_id) {
+		lines.push(format!(
+			"date: {:?}",
+			date.format(&args.post_date_format).to_string()
+		));
+	}
+	if let Some(author) = &args.post_author {
+		lines.push(format!("author: {}", author));
+	}
+	lines.push(format!("{}:", args.post_twitter_key));
+	lines.push(format!("  post: {}", like.expanded_url));
+
+	format!("---\n{}\n---\n\n{}\n", lines.join("\n"), content(like))
+}
+
+/// Build MarkDown, with FrontMatter, listing every like of `month`
+fn month_post(month: Option<(i32, u32)>, likes: &[&Like], args: &Args) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(format!("layout: {}", args.likes_post_layout));
+	if let Some(date) =
+		month.and_then(|(year, month)| Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single())
+	{
+		lines.push(format!(
+			"date: {:?}",
+			date.format(&args.post_date_format).to_string()
+		));
+		lines.push(format!("title: \"Likes of {}\"", date.format("%B %Y")));
+	}
+	if let Some(author) = &args.post_author {
+		lines.push(format!("author: {}", author));
+	}
+	lines.push(format!("{}:", args.post_twitter_key));
+	lines.push("  posts:".into());
+	for like in likes {
+		lines.push(format!("    - {}", like.expanded_url));
+	}
+
+	let body = likes
+		.iter()
+		.map(|like| content(like))
+		.collect::<Vec<String>>()
+		.join("\n\n---\n\n");
+
+	format!("---\n{}\n---\n\n{}\n", lines.join("\n"), body)
+}
+
+/// Liked text quoted, when archive still has it, followed by link to liked Tweet
+fn content(like: &Like) -> String {
+	let display_url = like
+		.expanded_url
+		.trim_start_matches("https://")
+		.trim_start_matches("http://");
+	let link = format!("-- [{}]({})", display_url, like.expanded_url);
+
+	match &like.full_text {
+		Some(full_text) if !full_text.trim().is_empty() => {
+			post_build::markdown_blockquote(&format!("{}\n\n{}", full_text, link))
+		}
+		_ => post_build::markdown_blockquote(&link),
+	}
+}
@@ -5,22 +5,44 @@
 #![forbid(unsafe_code)]
 #![deny(clippy::all, missing_docs)]
 
+mod accounts;
+mod archive;
 mod arguments;
+mod audit;
+mod context;
+mod direct_messages;
+mod dump;
+mod edits;
+mod graph;
+mod likes;
+mod media;
+mod merge;
+mod moments;
+mod note_tweets;
+mod output;
 mod post_build;
+mod profile;
+mod schema;
+mod sevenz;
+mod structs;
+mod tar;
+mod threads;
 
 use clap::CommandFactory;
 use clap::Parser;
-use std::io::{Read, Write};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::{env, fs, io, path};
 use twitter_archive::structs::manifest::Manifest;
-use twitter_archive::structs::tweets::TweetObject;
-use zip::read::ZipArchive;
 
-use arguments::Args;
+use archive::Archive;
+use arguments::{Args, DeletedPolicy, EditPolicy, RetweetPolicy};
+use context::Context;
+use structs::{NoteTweetObject, Tweet, TweetObject};
 
 /// Entry point for binary, this is where the magic starts and stops!
 fn main() -> io::Result<()> {
-	let args = Args::parse();
+	let mut args = Args::parse();
 
 	// Print shell completions and exit if requested
 	if let Some(shell) = args.build_completions {
@@ -29,6 +51,36 @@ fn main() -> io::Result<()> {
 		std::process::exit(0);
 	}
 
+	// Expand `--input-path` wildcards, eg. `twitter-*-part*.zip`, into every matching path
+	let input_paths = archive::expand_input_paths(&args.input_path);
+	if input_paths.is_empty() {
+		eprintln!(
+			"main -> No file matches --input-path -> {}",
+			args.input_path.join(" ")
+		);
+		std::process::exit(1);
+	}
+
+	// Report archive contents and exit if requested
+	if let Some(report_format) = args.audit {
+		let healthy = audit::print(&input_paths, report_format, &args)?;
+		std::process::exit(if healthy { 0 } else { 1 });
+	}
+
+	// Report drift between archived Tweets and parsing structs, then exit, if requested
+	if let Some(report_format) = args.schema_check {
+		let healthy = schema::print(&input_paths, report_format, &args)?;
+		std::process::exit(if healthy { 0 } else { 1 });
+	}
+
+	// Write data types, and profile page, only then exit, when no posts were asked for
+	if (args.dump_directory.is_some() || args.profile_page.is_some())
+		&& args.output_directory.is_none()
+	{
+		exports_to_files(&input_paths, &mut args)?;
+		std::process::exit(0);
+	}
+
 	// Use current working directory if output path is undefined
 	let output_directory = &args
 		.output_directory
@@ -53,76 +105,107 @@ fn main() -> io::Result<()> {
 		);
 	}
 
-	let input_path = path::Path::new(&args.input_path);
-	if input_path.is_file() {
+	// Create media path if none exists
+	if let Some(media_directory) = &args.media_directory {
+		let media_directory_path = path::Path::new(media_directory);
+		if !media_directory_path.is_dir() {
+			if args.verbose || args.dry_run {
+				eprintln!(
+					"main -> create media_directory_path -> {}",
+					media_directory_path.display()
+				);
+			} else {
+				fs::create_dir_all(media_directory_path)?;
+			}
+		}
+	}
+
+	// Several exports of one account, rather than parts of one archive, are merged, while those
+	// of several accounts are kept apart
+	let mut accounts = match input_paths.len() {
+		1 => vec![],
+		_ => merge::accounts(merge::exports(&input_paths)?),
+	};
+
+	let input_path = input_paths[0].as_path();
+	if accounts.len() > 1 {
+		accounts_to_markdown(accounts, output_directory_path, &args)?;
+	} else if accounts.first().is_some_and(|exports| exports.len() > 1) {
+		let exports = accounts.remove(0);
+		merged_to_markdown(exports, output_directory_path, &mut args)?;
+	} else if input_paths.len() > 1 && input_paths.iter().any(|path| !path.is_file()) {
+		eprintln!("main -> Several --input-path values must all be packed archives, eg. zip files");
+		std::process::exit(1);
+	} else if accounts
+		.first()
+		.is_some_and(|exports| merge::has_manifest(exports))
+	{
+		// Parts of one packed archive, already opened while grouping them
+		archive_to_markdown(
+			merge::into_archive(accounts.remove(0)),
+			None,
+			output_directory_path,
+			&mut args,
+		)?;
+	} else if input_path.is_file() && archive::is_packed(input_path) {
+		archive_to_markdown(
+			Archive::packed(&input_paths)?,
+			None,
+			output_directory_path,
+			&mut args,
+		)?;
+	} else if input_path.is_file() {
 		let extension = input_path
 			.extension()
 			.and_then(std::ffi::OsStr::to_str)
-			.unwrap();
+			.unwrap_or_default();
 
 		match extension {
-			"zip" => {
-				let javascript_manfifest =
-					read_zip_by_name_to_string(&args.input_path, "data/manifest.js", &args);
-
-				let json_manifest = javascript_manfifest.replacen("window.__THAR_CONFIG = ", "", 1);
-
-				let data_manifest: Manifest =
-					serde_json::from_str(&json_manifest).expect("Unable to parse string as JSON");
-
-				data_manifest
-					.data_types
-					.tweets
-					.files
-					.iter()
-					.for_each(|data_manifest_tweets| {
-						let file_name = &data_manifest_tweets.file_name;
-						let pattern = format!("window.{} = ", data_manifest_tweets.global_name);
-
-						let javascript_tweets =
-							read_zip_by_name_to_string(&args.input_path, file_name, &args);
-
-						let json_tweets = javascript_tweets.replacen(&pattern, "", 1);
-
-						if args.verbose {
-							eprintln!(
-								"main is_file zip:\n  file_name: {file_name}\n  pattern: {pattern}"
-							);
-						}
-
-						let data_tweets: Vec<TweetObject> =
-							serde_json::from_str(&json_tweets).expect("Unable to parse as JSON");
-
-						tweets_to_markdown(&data_tweets, output_directory_path, &args).unwrap();
-					});
-			}
 			"js" => {
 				if args.verbose {
-					eprintln!("main is_file js:\n  input_path: {}", args.input_path);
+					eprintln!("main is_file js:\n  input_path: {}", input_path.display());
 				}
 
 				let javascript_tweets =
 					fs::read_to_string(input_path).expect("Unable to read --input-path");
 
-				let json_tweets = javascript_tweets.replacen(&args.javascript_pattern, "", 1);
+				let javascript_tweets = match &args.javascript_pattern {
+					Some(pattern) => javascript_tweets
+						.replacen(pattern, "", 1)
+						.trim_start()
+						.trim_start_matches('=')
+						.to_string(),
+					None => javascript_tweets,
+				};
+
+				let file_name = input_path.file_name().unwrap().to_string_lossy();
+				let (_, json_tweets) = archive::strip_assignment(&javascript_tweets);
 
-				let data_tweets: Vec<TweetObject> =
-					serde_json::from_str(&json_tweets).expect("Unable to parse as JSON");
+				let data_tweets: Vec<TweetObject> = with_source_file(
+					&file_name,
+					archive::parse_json(&file_name, json_tweets).expect("Unable to parse as JSON"),
+				);
 
-				tweets_to_markdown(&data_tweets, output_directory_path, &args).unwrap();
+				let mut context = context_sibling_media(input_path, output_directory_path, &args);
+
+				tweets_to_markdown(&data_tweets, &mut context, &args).unwrap();
 			}
 			"json" => {
 				if args.verbose {
-					eprintln!("main is_file json:\n  input_path: {}", args.input_path);
+					eprintln!("main is_file json:\n  input_path: {}", input_path.display());
 				}
 
 				let json_tweets =
-					fs::read_to_string(&args.input_path).expect("Unable to read --input-path");
+					fs::read_to_string(input_path).expect("Unable to read --input-path");
+
+				let data_tweets: Vec<TweetObject> = with_source_file(
+					&input_path.file_name().unwrap().to_string_lossy(),
+					serde_json::from_str(&json_tweets).expect("Unable to parse as JSON"),
+				);
 
-				let data_tweets: Vec<TweetObject> =
-					serde_json::from_str(&json_tweets).expect("Unable to parse as JSON");
+				let mut context = context_sibling_media(input_path, output_directory_path, &args);
 
-				tweets_to_markdown(&data_tweets, output_directory_path, &args).unwrap();
+				tweets_to_markdown(&data_tweets, &mut context, &args).unwrap();
 			}
 			_ => {
 				let mut cmd = Args::command();
@@ -133,54 +216,23 @@ fn main() -> io::Result<()> {
 			}
 		}
 	} else if input_path.is_dir() {
-		let path_manifest = input_path.join("data").join("manifest.js");
-
-		if args.verbose {
-			eprintln!("main is_dir:\n  path_manifest: {}", path_manifest.display());
+		if input_path.join("data").join("manifest.js").is_file() {
+			archive_to_markdown(
+				Archive::directory(input_path),
+				None,
+				output_directory_path,
+				&mut args,
+			)?;
+		} else {
+			loose_files_to_markdown(input_path, output_directory_path, &args)?;
 		}
-
-		let javascript_manfifest = fs::read_to_string(path_manifest.to_str().unwrap())
-			.unwrap_or_else(|_| panic!("Unable to read manifest path: {}", path_manifest.display()));
-
-		let json_manifest = javascript_manfifest.replacen("window.__THAR_CONFIG = ", "", 1);
-
-		let data_manifest: Manifest = serde_json::from_str(&json_manifest)
-			.unwrap_or_else(|_| panic!("Unable to parse manifest path: {}", path_manifest.display()));
-
-		data_manifest
-			.data_types
-			.tweets
-			.files
-			.iter()
-			.for_each(|data_manifest_tweets| {
-				let file_name = &data_manifest_tweets.file_name;
-				let pattern = format!("window.{} = ", data_manifest_tweets.global_name);
-
-				let mut javascript_path = path::PathBuf::from(input_path);
-				file_name.split('/').for_each(|p| {
-					javascript_path.push(p);
-				});
-
-				if args.verbose {
-					eprintln!("main is_dir:\n  file_name: {file_name}\n  pattern: {pattern}\n  javascript: {}", javascript_path.display());
-				}
-
-				let javascript_tweets = fs::read_to_string(&javascript_path).unwrap();
-
-				let json_tweets = javascript_tweets.replacen(&pattern, "", 1);
-
-				let data_tweets: Vec<TweetObject> =
-					serde_json::from_str(&json_tweets).expect("Unable to parse as JSON");
-
-				tweets_to_markdown(&data_tweets, output_directory_path, &args).unwrap();
-			});
 	} else {
 		let mut cmd = Args::command();
 		cmd.build();
 		cmd.print_help().unwrap();
 		eprintln!(
 			"main -> Unexpected file extension or path type for --input-path -> {}",
-			args.input_path
+			input_path.display()
 		);
 		std::process::exit(1);
 	}
@@ -188,19 +240,153 @@ fn main() -> io::Result<()> {
 	Ok(())
 }
 
-/// Create a file for each Tweet that does not yet have a corresponding MarkDown file
-pub fn tweets_to_markdown(
-	data_tweets: &[twitter_archive::structs::tweets::TweetObject],
+/// Convert every supported data type of an `archive`, packed or unpacked
+///
+/// Deleted Tweets are written with their own `Context`, which takes `archive` over once others
+/// are done, and `merged_tweets`, when provided, replace Tweets listed by manifest.
+fn archive_to_markdown(
+	archive: Archive,
+	merged_tweets: Option<Vec<TweetObject>>,
 	output_directory_path: &path::Path,
+	args: &mut Args,
+) -> io::Result<()> {
+	let data_manifest = archive
+		.manifest()
+		.expect("Unable to parse data/manifest.js");
+
+	let mut data_tweets = match merged_tweets {
+		Some(data_tweets) => data_tweets,
+		None => archive
+			.read_data_by_file::<TweetObject>(&data_manifest.data_types.tweets.files, args)
+			.expect("Unable to read Tweets")
+			.into_iter()
+			.flat_map(|(file_name, data_tweets)| with_source_file(&file_name, data_tweets))
+			.collect::<Vec<TweetObject>>(),
+	};
+
+	default_post_author(&mut args.post_author, &data_manifest);
+	let args = &*args;
+
+	let mut context = Context::new(
+		Some(archive),
+		&data_manifest.data_types.tweets_media.media_directory,
+		output_directory_path,
+		args,
+	);
+	context.index_manifest(&data_manifest);
+	context.screen_names = read_screen_names(&context, &data_manifest, args);
+
+	let community_tweets = read_community_tweets(&mut context, &data_manifest, args);
+	append_community_tweets(&mut data_tweets, community_tweets, args);
+
+	let notes = read_note_tweets(&context, &data_manifest, args);
+	note_tweets::apply(&mut data_tweets, &notes, args);
+
+	tweets_to_markdown(&data_tweets, &mut context, args)?;
+	moments::to_markdown(&data_manifest, &mut context, args)?;
+
+	if let Some(archive) = &context.archive {
+		likes::to_markdown(archive, &data_manifest, output_directory_path, args)?;
+		direct_messages::to_markdown(archive, &data_manifest, &context, args)?;
+		profile::to_markdown(archive, &data_manifest, args)?;
+		graph::to_files(archive, &data_manifest, &data_tweets, args)?;
+		dump::to_files(archive, Some(output_directory_path), args)?;
+	}
+
+	if let Some(archive) = context.archive.take() {
+		deleted_tweets_to_markdown(archive, &data_manifest, output_directory_path, args)?;
+	}
+
+	Ok(())
+}
+
+/// Write every data type into `--dump-directory`, and profile into `--profile-page`, of archive
+/// at `input_paths` without converting Tweets, when `--output-directory` is undefined
+fn exports_to_files(input_paths: &[path::PathBuf], args: &mut Args) -> io::Result<()> {
+	if !input_paths
+		.iter()
+		.all(|input_path| input_path.is_dir() || archive::is_packed(input_path))
+	{
+		eprintln!(
+			"main -> --dump-directory and --profile-page require an archive, packed or unpacked, as --input-path"
+		);
+		std::process::exit(1);
+	}
+
+	let archive = Archive::open(input_paths)?;
+	dump::to_files(&archive, None, args)?;
+
+	if args.profile_page.is_none() {
+		return Ok(());
+	}
+
+	let data_manifest = match archive.manifest() {
+		Ok(data_manifest) => data_manifest,
+		Err(error) => {
+			eprintln!("main -> Skipping --profile-page -> {error}");
+			return Ok(());
+		}
+	};
+	default_post_author(&mut args.post_author, &data_manifest);
+
+	if let Some(media_directory) = &args.media_directory {
+		output::create_directory(path::Path::new(media_directory), args)?;
+	}
+
+	profile::to_markdown(&archive, &data_manifest, args)
+}
+
+/// Create a file for each Tweet, or thread of Tweets when `--threads` is set, that does not yet
+/// have a corresponding MarkDown file
+pub fn tweets_to_markdown(
+	data_tweets: &[TweetObject],
+	context: &mut Context,
 	args: &Args,
 ) -> io::Result<()> {
-	for (index, object) in data_tweets.iter().enumerate() {
+	let tweets = data_tweets
+		.iter()
+		.map(|object| &object.tweet)
+		.collect::<Vec<&Tweet>>();
+
+	let (tweets, edits) = match args.edits {
+		EditPolicy::Separate => (tweets, HashMap::new()),
+		_ => edits::collapse(tweets, args),
+	};
+
+	let mut groups = match args.threads {
+		true => threads::group(&tweets, args),
+		false => tweets.iter().map(|tweet| vec![*tweet]).collect(),
+	};
+
+	if args.retweets == RetweetPolicy::Skip {
+		groups.retain(|group| {
+			let is_retweet = group[0].retweet_of().is_some();
+			if is_retweet && args.verbose {
+				eprintln!(
+					"tweets_to_markdown -> Skipping retweet -> {}",
+					group[0].id_str
+				);
+			}
+			!is_retweet
+		});
+	}
+
+	context.index_posts(&groups, args);
+	context.index_edits(&edits, args);
+
+	for (index, group) in groups.iter().enumerate() {
 		if args.verbose {
 			eprintln!("tweets_to_markdown -> Parsing Tweet index -> {index}");
 		}
 
-		let markdown_file_name = post_build::file_name(&object.tweet, args);
-		let markdown_file_path = output_directory_path.join(markdown_file_name);
+		context.directory = post_build::directory(group[0], args);
+		let post_directory_path = context.post_directory();
+		if !post_directory_path.is_dir() && !args.dry_run {
+			fs::create_dir_all(&post_directory_path)?;
+		}
+
+		let markdown_file_name = post_build::file_name(group[0], args);
+		let markdown_file_path = post_directory_path.join(markdown_file_name);
 		if markdown_file_path.is_file() {
 			eprintln!(
 				"tweets_to_markdown -> Skipping existing file -> {}",
@@ -209,7 +395,26 @@ pub fn tweets_to_markdown(
 			continue;
 		}
 
-		let post = post_build::post(&object.tweet, args);
+		let tombstone =
+			args.deleted_tweets == DeletedPolicy::Tombstone && group[0].deleted_at.is_some();
+		let retweet_link = args.retweets == RetweetPolicy::Link && group[0].retweet_of().is_some();
+
+		if let (Some(archive), false) = (&context.archive, tombstone || retweet_link) {
+			for tweet in group {
+				for media in tweet.media() {
+					for file_name in context.media_files(&tweet.id_str, media, args) {
+						media::copy(archive, file_name, args)?;
+					}
+				}
+			}
+		}
+
+		let post = match (group.as_slice(), tombstone, retweet_link) {
+			(thread, true, _) => post_build::tombstone(thread, args),
+			([tweet, ..], false, true) => post_build::retweet_link(tweet, args),
+			([tweet], false, false) => post_build::post(tweet, context, args),
+			(thread, false, _) => post_build::thread(thread, context, args),
+		};
 
 		if args.dry_run {
 			println!("{}", post);
@@ -225,17 +430,343 @@ pub fn tweets_to_markdown(
 	Ok(())
 }
 
-/// Load contents of `zip_path:file_name` into returned String
-pub fn read_zip_by_name_to_string(zip_path: &str, file_name: &str, _args: &Args) -> String {
-	let zip_file_descriptor = fs::File::open(zip_path).expect("Unable to read input_path");
+/// Convert exports of each account within its own subdirectory of `--output-directory`, then list
+/// every account within `--accounts-index`
+fn accounts_to_markdown(
+	accounts: Vec<Vec<merge::Export>>,
+	output_directory_path: &path::Path,
+	args: &Args,
+) -> io::Result<()> {
+	accounts::warn_shared_paths(args);
+
+	let mut index: Vec<accounts::Account> = vec![];
+	for exports in accounts {
+		let Some(user_info) = merge::user_info(&exports).cloned() else {
+			continue;
+		};
+
+		let mut account_args = accounts::args(&user_info.user_name, output_directory_path, args);
+		let account_output_directory_path =
+			path::PathBuf::from(account_args.output_directory.clone().unwrap_or_default());
+		output::create_directory(&account_output_directory_path, &account_args)?;
+
+		if args.verbose {
+			eprintln!(
+				"main -> Converting account -> {} -> {}",
+				user_info.user_name,
+				account_output_directory_path.display()
+			);
+		}
+
+		index.push(accounts::Account {
+			generation_date: merge::generation_date(&exports),
+			directory: account_output_directory_path.clone(),
+			exports: exports.len(),
+			user_info,
+		});
+
+		merged_to_markdown(exports, &account_output_directory_path, &mut account_args)?;
+	}
+
+	accounts::write_index(&index, args)
+}
+
+/// Convert Tweets merged from several `exports` of one account, reading everything else, such as
+/// likes and profile, from newest archive while media is copied from whichever export holds it
+fn merged_to_markdown(
+	exports: Vec<merge::Export>,
+	output_directory_path: &path::Path,
+	args: &mut Args,
+) -> io::Result<()> {
+	let data_tweets = merge::read_tweets(&exports, args)?;
+
+	if !merge::has_manifest(&exports) {
+		let mut context = Context::new(
+			Some(merge::into_archive(exports)),
+			"tweets_media",
+			output_directory_path,
+			args,
+		);
+		return tweets_to_markdown(&data_tweets, &mut context, args);
+	}
+
+	archive_to_markdown(
+		merge::into_archive(exports),
+		Some(data_tweets),
+		output_directory_path,
+		args,
+	)
+}
+
+/// Convert loose JavaScript files, eg. `tweets.js`, `tweets-part1.js`, and `note-tweet.js`, found
+/// directly within `input_path` directory that has no `data/manifest.js`
+///
+/// Data type of each file is inferred from its `window.YTD.<data_type>.partN` assignment, and
+/// media is looked for within `tweets_media` and `community_tweet_media` directories beside them.
+fn loose_files_to_markdown(
+	input_path: &path::Path,
+	output_directory_path: &path::Path,
+	args: &Args,
+) -> io::Result<()> {
+	let mut file_paths = fs::read_dir(input_path)?
+		.filter_map(Result::ok)
+		.map(|entry| entry.path())
+		.filter(|file_path| file_path.is_file())
+		.filter(|file_path| {
+			file_path
+				.extension()
+				.is_some_and(|extension| extension == "js")
+		})
+		.collect::<Vec<path::PathBuf>>();
+	file_paths.sort();
+
+	let mut data_tweets: Vec<TweetObject> = vec![];
+	let mut community_tweets: Vec<TweetObject> = vec![];
+	let mut notes: Vec<NoteTweetObject> = vec![];
+
+	for file_path in file_paths {
+		let file_name = file_path.file_name().unwrap().to_string_lossy().to_string();
+		let javascript = fs::read_to_string(&file_path)?;
+		let (identifier, json) = archive::strip_assignment(&javascript);
+		let data_type = identifier.and_then(archive::data_type_of);
+
+		if args.verbose {
+			eprintln!(
+				"main is_dir loose:\n  file_name: {file_name}\n  data_type: {}",
+				data_type.unwrap_or_default()
+			);
+		}
+
+		match data_type {
+			Some("tweets") => {
+				data_tweets.extend(with_source_file(
+					&file_name,
+					archive::parse_json(&file_name, json)?,
+				));
+			}
+			Some("community_tweet") => {
+				community_tweets.extend(with_source_file(
+					&file_name,
+					archive::parse_json(&file_name, json)?,
+				));
+			}
+			Some("note_tweet") => {
+				notes.extend(archive::parse_json::<Vec<NoteTweetObject>>(
+					&file_name, json,
+				)?);
+			}
+			_ => {
+				eprintln!("main -> Skipping loose file of unsupported data type -> {file_name}");
+			}
+		}
+	}
+
+	community_tweets
+		.iter_mut()
+		.for_each(|object| object.tweet.community_tweet = true);
+
+	let mut context = Context::new(
+		Some(Archive::directory(input_path)),
+		"tweets_media",
+		output_directory_path,
+		args,
+	);
+	context.index_media("community_tweet_media", args);
+
+	append_community_tweets(&mut data_tweets, community_tweets, args);
+	note_tweets::apply(&mut data_tweets, &notes, args);
+
+	tweets_to_markdown(&data_tweets, &mut context, args)
+}
+
+/// Write Tweets listed by archive `data/deleted-tweets.js` when `--deleted-tweets` is not `skip`
+///
+/// Posts, and any media copied for them, are written to `--deleted-output-directory`, so nothing
+/// about deleted Tweets lands within `--output-directory` or `--media-directory`
+fn deleted_tweets_to_markdown(
+	archive: Archive,
+	data_manifest: &Manifest,
+	output_directory_path: &path::Path,
+	args: &Args,
+) -> io::Result<()> {
+	if args.deleted_tweets == DeletedPolicy::Skip {
+		return Ok(());
+	}
+
+	let deleted_tweets = &data_manifest.data_types.deleted_tweets;
+	let mut data_tweets = archive
+		.read_data_by_file(&deleted_tweets.files, args)?
+		.into_iter()
+		.flat_map(|(file_name, data_tweets)| with_source_file(&file_name, data_tweets))
+		.collect::<Vec<TweetObject>>();
+	data_tweets.retain(|object| object.tweet.deleted_at.is_some());
+
+	if data_tweets.is_empty() {
+		if args.verbose {
+			eprintln!("main -> No deleted Tweets found within archive");
+		}
+		return Ok(());
+	}
+
+	let Some(deleted_output_directory) = args.deleted_output_directory.clone() else {
+		eprintln!("main -> Refusing to write deleted Tweets without --deleted-output-directory");
+		return Ok(());
+	};
+
+	// Media of deleted Tweets is kept beside their posts instead of within `--media-directory`
+	let deleted_output_directory_path = path::Path::new(&deleted_output_directory);
+	if let Some(public_directory) =
+		output::public_directory_of(deleted_output_directory_path, output_directory_path, args)
+	{
+		eprintln!(
+			"main -> Refusing to write deleted Tweets within -> {}",
+			public_directory.display()
+		);
+		return Ok(());
+	}
+	let args = Args {
+		output_directory: Some(deleted_output_directory.clone()),
+		media_directory: args
+			.media_directory
+			.as_ref()
+			.filter(|_| args.deleted_tweets == DeletedPolicy::Include)
+			.map(|_| {
+				deleted_output_directory_path
+					.join("media")
+					.display()
+					.to_string()
+			}),
+		media_url_prefix: None,
+		post_permalink: None,
+		..args.clone()
+	};
+
+	output::create_directory(deleted_output_directory_path, &args)?;
+	if let Some(media_directory) = &args.media_directory {
+		output::create_directory(path::Path::new(media_directory), &args)?;
+	}
+
+	let mut context = Context::new(
+		Some(archive),
+		&deleted_tweets.media_directory,
+		deleted_output_directory_path,
+		&args,
+	);
+	context.index_manifest(data_manifest);
+	context.screen_names = read_screen_names(&context, data_manifest, &args);
+
+	tweets_to_markdown(&data_tweets, &mut context, &args)
+}
+
+/// Look for `tweets_media` directory next to `.js` or `.json` input file, as found within an
+/// unpacked archive's `data` directory
+fn context_sibling_media(
+	input_path: &path::Path,
+	output_directory_path: &path::Path,
+	args: &Args,
+) -> Context {
+	let archive = input_path.parent().map(Archive::directory);
+
+	Context::new(archive, "tweets_media", output_directory_path, args)
+}
+
+/// Record archive `file_name` that each of `data_tweets` was loaded from
+fn with_source_file(file_name: &str, mut data_tweets: Vec<TweetObject>) -> Vec<TweetObject> {
+	data_tweets
+		.iter_mut()
+		.for_each(|object| object.tweet.source_file = Some(file_name.to_string()));
+
+	data_tweets
+}
+
+/// Use archive account's user name for `--post-author` when none was provided
+fn default_post_author(post_author: &mut Option<String>, data_manifest: &Manifest) {
+	post_author.get_or_insert_with(|| data_manifest.user_info.user_name.clone());
+}
+
+/// Load Tweets made within X Communities listed by manifest, and index their media, warning
+/// instead of failing when unreadable
+fn read_community_tweets(
+	context: &mut Context,
+	data_manifest: &Manifest,
+	args: &Args,
+) -> Vec<TweetObject> {
+	let Some(archive) = &context.archive else {
+		return vec![];
+	};
+
+	let community_tweet = &data_manifest.data_types.community_tweet;
+	let mut community_tweets = archive
+		.read_data_by_file(&community_tweet.files, args)
+		.unwrap_or_else(|error| {
+			eprintln!("main -> Unable to read community Tweets -> {error}");
+			vec![]
+		})
+		.into_iter()
+		.flat_map(|(file_name, data_tweets)| with_source_file(&file_name, data_tweets))
+		.collect::<Vec<TweetObject>>();
+
+	community_tweets
+		.iter_mut()
+		.for_each(|object| object.tweet.community_tweet = true);
+
+	context.index_media(&community_tweet.media_directory, args);
+
+	community_tweets
+}
+
+/// Interleave community Tweets with those of `data/tweets.js`, so threads and replies resolve
+/// across both, skipping any Tweet already listed
+fn append_community_tweets(
+	data_tweets: &mut Vec<TweetObject>,
+	community_tweets: Vec<TweetObject>,
+	args: &Args,
+) {
+	let ids = data_tweets
+		.iter()
+		.map(|object| object.tweet.id_str.clone())
+		.collect::<HashSet<String>>();
+
+	for object in community_tweets {
+		if ids.contains(&object.tweet.id_str) {
+			if args.verbose {
+				eprintln!(
+					"main -> Skipping duplicate community Tweet -> {}",
+					object.tweet.id_str
+				);
+			}
+			continue;
+		}
+		data_tweets.push(object);
+	}
+}
+
+/// Load long-form Tweet notes listed by manifest, warning instead of failing when unreadable
+fn read_note_tweets(
+	context: &Context,
+	data_manifest: &Manifest,
+	args: &Args,
+) -> Vec<NoteTweetObject> {
+	let Some(archive) = &context.archive else {
+		return vec![];
+	};
+
+	archive
+		.read_data(&data_manifest.data_types.note_tweet.files, args)
+		.unwrap_or_else(|error| {
+			eprintln!("main -> Unable to read note Tweets -> {error}");
+			vec![]
+		})
+}
 
-	let mut buffer = String::new();
-	ZipArchive::new(zip_file_descriptor)
-		.unwrap()
-		.by_name(file_name)
-		.unwrap()
-		.read_to_string(&mut buffer)
-		.unwrap();
+/// Load former handles of account listed by manifest, warning instead of failing when unreadable
+fn read_screen_names(context: &Context, data_manifest: &Manifest, args: &Args) -> Vec<String> {
+	let Some(archive) = &context.archive else {
+		return vec![];
+	};
 
-	buffer
+	profile::screen_names(archive, data_manifest, args).unwrap_or_else(|error| {
+		eprintln!("main -> Unable to read screen name changes -> {error}");
+		vec![]
+	})
 }
@@ -0,0 +1,197 @@
+#!/usr/bin/env rust
+
+//! Find, and copy, Tweet media files stored within archive `data/tweets_media` directory
+//!
+//! Archive media file names are prefixed by the Tweet ID they belong to, followed by the base
+//! name of the remote media URL, eg.
+//!
+//!   data/tweets_media/1697011324369178968-F4zXYZaWcAA1b2c.jpg
+
+use std::collections::HashMap;
+use std::{env, io, path};
+
+use crate::archive::Archive;
+use crate::arguments::Args;
+use crate::structs::TweetMedia;
+
+/// Archive media file names grouped by the Tweet ID that prefixes them
+#[derive(Debug, Default)]
+pub struct MediaIndex {
+	/// Map of Tweet ID to archive file names, eg. `data/tweets_media/<tweet id>-<name>.jpg`
+	files: HashMap<String, Vec<String>>,
+}
+
+impl MediaIndex {
+	/// Group all file names under archive `media_directory` by Tweet ID
+	pub fn new(archive: &Archive, media_directory: &str, args: &Args) -> Self {
+		let mut media_index = Self::default();
+		media_index.extend(archive, media_directory, args);
+		media_index
+	}
+
+	/// Add file names under another archive `media_directory`, eg. `data/community_tweet_media`
+	pub fn extend(&mut self, archive: &Archive, media_directory: &str, args: &Args) {
+		for file_name in archive.file_names(media_directory) {
+			if let Some((tweet_id, _)) = base_name(&file_name).split_once('-') {
+				self.files
+					.entry(tweet_id.to_string())
+					.or_default()
+					.push(file_name.clone());
+			}
+		}
+
+		if args.verbose {
+			eprintln!(
+				"media::MediaIndex::extend -> {media_directory} -> Tweets with media: {}",
+				self.files.len()
+			);
+		}
+	}
+
+	/// Archive file name of `media` attached to Tweet with `tweet_id`, if one was exported
+	///
+	/// For `video` and `animated_gif` media the first locally available variant is chosen
+	pub fn find(&self, tweet_id: &str, media: &TweetMedia) -> Option<&str> {
+		match &media.video_info {
+			Some(video_info) if media.is_video() => video_info
+				.variants
+				.iter()
+				.find_map(|variant| self.find_remote(tweet_id, &variant.url)),
+			_ => self.find_remote(tweet_id, &media.media_url_https),
+		}
+	}
+
+	/// Archive file name of remote `url` attached to Tweet with `tweet_id`, if one was exported
+	///
+	/// Remote URLs without file extension, eg. profile banners, match archive files that add one
+	pub fn find_remote(&self, tweet_id: &str, url: &str) -> Option<&str> {
+		let files = self.files.get(tweet_id)?;
+		let expected = format!("{tweet_id}-{}", url_base_name(url));
+
+		files
+			.iter()
+			.find(|file_name| base_name(file_name) == expected)
+			.or_else(|| {
+				files.iter().find(|file_name| {
+					base_name(file_name)
+						.strip_prefix(&expected)
+						.is_some_and(|extension| extension.starts_with('.'))
+				})
+			})
+			.map(String::as_str)
+	}
+}
+
+/// Copy archive `file_name` into `--media-directory`, unless a file of same name exists
+pub fn copy(archive: &Archive, file_name: &str, args: &Args) -> io::Result<()> {
+	let Some(media_directory) = &args.media_directory else {
+		return Ok(());
+	};
+
+	let destination = path::Path::new(media_directory).join(base_name(file_name));
+	if destination.is_file() {
+		if args.verbose {
+			eprintln!(
+				"media::copy -> Skipping existing file -> {}",
+				destination.display()
+			);
+		}
+		return Ok(());
+	}
+
+	if args.dry_run {
+		eprintln!("media::copy -> {file_name} -> {}", destination.display());
+		return Ok(());
+	}
+
+	archive.copy(file_name, &destination)?;
+	if !args.verbose {
+		eprintln!("Wrote file -> {}", destination.display());
+	}
+
+	Ok(())
+}
+
+/// URL path that posts should use to embed archive `file_name` once copied
+///
+/// Uses `--media-url-prefix` when defined, otherwise `--media-directory` relative to
+/// `output_directory_path`
+pub fn url(file_name: &str, output_directory_path: &path::Path, args: &Args) -> String {
+	let prefix = match &args.media_url_prefix {
+		Some(prefix) => prefix.trim_end_matches('/').to_string(),
+		None => {
+			let media_directory = args.media_directory.clone().unwrap_or_default();
+			relative_path(output_directory_path, path::Path::new(&media_directory))
+		}
+	};
+
+	if prefix.is_empty() {
+		base_name(file_name).to_string()
+	} else {
+		format!("{prefix}/{}", base_name(file_name))
+	}
+}
+
+/// Whether archive `file_name` names a video, eg. `.mp4`, rather than an image
+pub fn is_video_file(file_name: &str) -> bool {
+	let extension = base_name(file_name)
+		.rsplit_once('.')
+		.map(|(_, extension)| extension.to_lowercase())
+		.unwrap_or_default();
+
+	matches!(extension.as_str(), "mp4" | "mov" | "webm" | "m4v")
+}
+
+/// Last `/` separated component of archive file name
+pub fn base_name(file_name: &str) -> &str {
+	file_name.rsplit('/').next().unwrap_or(file_name)
+}
+
+/// Last path component of URL with any query string removed
+pub fn url_base_name(url: &str) -> &str {
+	let url = url.split(['?', '#']).next().unwrap_or(url);
+	base_name(url)
+}
+
+/// Build `/` separated path that leads from `from` directory to `to` directory
+pub fn relative_path(from: &path::Path, to: &path::Path) -> String {
+	let from = normalize(from);
+	let to = normalize(to);
+
+	let common = from
+		.iter()
+		.zip(to.iter())
+		.take_while(|(a, b)| a == b)
+		.count();
+
+	let mut parts: Vec<String> = vec![];
+	from.iter()
+		.skip(common)
+		.for_each(|_| parts.push("..".into()));
+	to.iter()
+		.skip(common)
+		.for_each(|part| parts.push(part.clone()));
+
+	parts.join("/")
+}
+
+/// Absolute, lexically cleaned, list of path components
+pub fn normalize(directory: &path::Path) -> Vec<String> {
+	let directory = match directory.is_absolute() {
+		true => directory.to_path_buf(),
+		false => env::current_dir().unwrap_or_default().join(directory),
+	};
+
+	let mut result: Vec<String> = vec![];
+	for component in directory.components() {
+		match component {
+			path::Component::Normal(part) => result.push(part.to_string_lossy().to_string()),
+			path::Component::ParentDir => {
+				result.pop();
+			}
+			_ => {}
+		}
+	}
+
+	result
+}
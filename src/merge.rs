@@ -0,0 +1,400 @@
+#!/usr/bin/env rust
+
+//! Merge several exports of one account, eg. archives downloaded months apart, into one set of
+//! Tweets keyed by `id_str`
+//!
+//! Exports are ranked by manifest `generationDate`, and where two disagree the most recent wins
+//! for each field, while Tweets only older exports still hold are kept.  Plain `.js` or `.json`
+//! inputs have no manifest, so rank below every archive in order given.
+//!
+//! Exports of different accounts, by manifest `userInfo.accountId`, are never merged together.
+
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::{io, path};
+use twitter_archive::structs::manifest::{self, ArchiveInfo, UserInfo};
+
+use crate::archive::{self, Archive};
+use crate::arguments::Args;
+use crate::structs::TweetObject;
+
+/// Manifest `generationDate` and `userInfo.accountId` shared by packed file parts of one archive
+type PackedKey = (DateTime<Utc>, String);
+
+/// Packed file named by `--input-path`, opened as an archive of its own until grouped with others
+type PackedPart = (path::PathBuf, Archive);
+
+/// Packed file parts of one archive, with the untyped manifest one of them holds
+struct PackedGroup {
+	/// Identifies parts with a manifest that belong together
+	key: PackedKey,
+
+	/// Archive names, eg. `twitter-2023-08-31-abc`, that parts without a manifest are matched by
+	archive_names: Vec<String>,
+
+	/// Manifest of first part that holds one
+	data_manifest: serde_json::Value,
+
+	/// Every part, in order given
+	parts: Vec<PackedPart>,
+}
+
+/// One export of account, as named by one or more `--input-path` values
+pub struct Export {
+	/// Where files of export, such as Tweets and media, are read from
+	archive: Archive,
+
+	/// When archive was generated, `None` for plain `.js` or `.json` inputs
+	generation_date: Option<DateTime<Utc>>,
+
+	/// Files, relative to archive, that list Tweets
+	tweet_files: Vec<String>,
+
+	/// Account that requested archive, `None` for plain `.js` or `.json` inputs
+	user_info: Option<UserInfo>,
+
+	/// `--input-path` values export was read from
+	input_paths: Vec<path::PathBuf>,
+}
+
+/// Group `input_paths` into exports, newest first
+///
+/// Manifests are read untyped, so exports older, or newer, than `twitter_archive` structs are
+/// still ranked by their own `generationDate`.  Packed files, eg. zip, with same
+/// `generationDate` and account are parts of one archive instead of separate exports, while parts
+/// without `data/manifest.js` belong to archive of same name, eg. `twitter-<date>-<hash>-part2.zip`
+/// to `twitter-<date>-<hash>-part1.zip`, in any order given.
+pub fn exports(input_paths: &[path::PathBuf]) -> io::Result<Vec<Export>> {
+	let mut result: Vec<Export> = vec![];
+	let mut packed_groups: Vec<PackedGroup> = vec![];
+	let mut unclaimed_parts: Vec<PackedPart> = vec![];
+
+	for input_path in input_paths {
+		if input_path.is_file() && archive::is_packed(input_path) {
+			// Each part is opened, and for tar decompressed, only once then reused by its export
+			let archive = Archive::packed(std::slice::from_ref(input_path))?;
+			if archive.file_size("data/manifest.js").is_none() {
+				unclaimed_parts.push((input_path.clone(), archive));
+				continue;
+			}
+
+			let data_manifest = archive.manifest_value()?;
+			let key = packed_key(&data_manifest).map_err(|error| {
+				io::Error::new(error.kind(), format!("{error} -> {}", input_path.display()))
+			})?;
+			let archive_name = archive_name(input_path);
+
+			match packed_groups.iter_mut().find(|group| group.key == key) {
+				Some(group) => {
+					group.archive_names.push(archive_name);
+					group.parts.push((input_path.clone(), archive));
+				}
+				None => packed_groups.push(PackedGroup {
+					key,
+					archive_names: vec![archive_name],
+					data_manifest,
+					parts: vec![(input_path.clone(), archive)],
+				}),
+			}
+			continue;
+		}
+
+		if input_path.is_dir() {
+			let archive = Archive::directory(input_path);
+			match archive.manifest_value() {
+				Ok(data_manifest) => result.push(manifest_export(
+					archive,
+					&data_manifest,
+					vec![input_path.clone()],
+				)?),
+				Err(error) => {
+					eprintln!(
+						"merge::exports -> Skipping directory without readable manifest -> {} -> {error}",
+						input_path.display()
+					);
+				}
+			}
+		} else if let (Some(parent), Some(file_name)) =
+			(input_path.parent(), input_path.file_name())
+		{
+			result.push(Export {
+				archive: Archive::directory(parent),
+				generation_date: None,
+				tweet_files: vec![file_name.to_string_lossy().to_string()],
+				user_info: None,
+				input_paths: vec![input_path.clone()],
+			});
+		}
+	}
+
+	for (unclaimed_path, archive) in unclaimed_parts {
+		let archive_name = archive_name(&unclaimed_path);
+		let Some(group) = packed_groups
+			.iter_mut()
+			.find(|group| group.archive_names.contains(&archive_name))
+		else {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!(
+					"No packed file with data/manifest.js is named after same archive, {archive_name}, as part -> {}",
+					unclaimed_path.display()
+				),
+			));
+		};
+		group.parts.push((unclaimed_path, archive));
+	}
+
+	for group in packed_groups {
+		result.push(packed_export(group)?);
+	}
+
+	// Stable sort keeps order given for exports generated at same time, or without a manifest
+	result.sort_by_key(|export| Reverse(export.generation_date));
+
+	Ok(result)
+}
+
+/// Group `exports` by account that requested them, in order of newest export of each
+///
+/// Plain `.js` or `.json` inputs are only kept when every archive belongs to one account, since
+/// nothing tells which account they came from otherwise.
+pub fn accounts(exports: Vec<Export>) -> Vec<Vec<Export>> {
+	let mut result: Vec<Vec<Export>> = vec![];
+	let mut unknown: Vec<Export> = vec![];
+
+	for export in exports {
+		let Some(account_id) = export
+			.user_info
+			.as_ref()
+			.map(|user_info| &user_info.account_id)
+		else {
+			unknown.push(export);
+			continue;
+		};
+
+		match result.iter_mut().find(|account| {
+			user_info(account).is_some_and(|user_info| &user_info.account_id == account_id)
+		}) {
+			Some(account) => account.push(export),
+			None => result.push(vec![export]),
+		}
+	}
+
+	match result.len() {
+		0 => vec![unknown],
+		1 => {
+			result[0].extend(unknown);
+			result
+		}
+		_ => {
+			for export in unknown {
+				eprintln!(
+					"merge::accounts -> Skipping input of unknown account among several -> {}",
+					input_paths(std::slice::from_ref(&export))
+						.iter()
+						.map(|input_path| input_path.display().to_string())
+						.collect::<Vec<String>>()
+						.join(" ")
+				);
+			}
+			result
+		}
+	}
+}
+
+/// Account that requested newest of `exports` with a manifest
+pub fn user_info(exports: &[Export]) -> Option<&UserInfo> {
+	exports.iter().find_map(|export| export.user_info.as_ref())
+}
+
+/// When newest of `exports` with a manifest was generated
+pub fn generation_date(exports: &[Export]) -> Option<DateTime<Utc>> {
+	exports.iter().find_map(|export| export.generation_date)
+}
+
+/// Every `--input-path` value that `exports` were read from
+pub fn input_paths(exports: &[Export]) -> Vec<path::PathBuf> {
+	exports
+		.iter()
+		.flat_map(|export| export.input_paths.iter().cloned())
+		.collect()
+}
+
+/// Whether any of `exports` has a manifest that account and archive details may be read from
+pub fn has_manifest(exports: &[Export]) -> bool {
+	exports
+		.iter()
+		.any(|export| export.generation_date.is_some())
+}
+
+/// Combine archives of `exports`, so media and other files are read from newest that holds them
+pub fn into_archive(mut exports: Vec<Export>) -> Archive {
+	match exports.len() {
+		1 => exports.remove(0).archive,
+		_ => Archive::Merged(exports.into_iter().map(|export| export.archive).collect()),
+	}
+}
+
+/// Load Tweets of every export, merging fields of those found within several
+///
+/// Each Tweet records the file, generation date, and `--input-path` values of the newest export
+/// that listed it, for `--provenance`.
+pub fn read_tweets(exports: &[Export], args: &Args) -> io::Result<Vec<TweetObject>> {
+	let mut merged: Vec<(serde_json::Value, String, &Export)> = vec![];
+	let mut ids: HashMap<String, usize> = HashMap::new();
+
+	for export in exports.iter().rev() {
+		for file_name in &export.tweet_files {
+			let javascript = export.archive.read_to_string(file_name)?;
+			let (_, json) = archive::strip_assignment(&javascript);
+			let data_tweets: Vec<serde_json::Value> = archive::parse_json(file_name, json)?;
+
+			if args.verbose {
+				eprintln!(
+					"merge::read_tweets:\n  generation_date: {}\n  file_name: {file_name}\n  tweets: {}",
+					export
+						.generation_date
+						.map(|date| date.to_rfc3339())
+						.unwrap_or_default(),
+					data_tweets.len()
+				);
+			}
+
+			for value in data_tweets {
+				let Some(id_str) = value
+					.pointer("/tweet/id_str")
+					.and_then(serde_json::Value::as_str)
+					.map(String::from)
+				else {
+					eprintln!(
+						"merge::read_tweets -> Skipping Tweet without id_str within {file_name}"
+					);
+					continue;
+				};
+
+				match ids.get(&id_str) {
+					Some(index) => {
+						let (older, source_file, source_export) = &mut merged[*index];
+						merge_value(older, value);
+						*source_file = file_name.clone();
+						*source_export = export;
+					}
+					None => {
+						ids.insert(id_str, merged.len());
+						merged.push((value, file_name.clone(), export));
+					}
+				}
+			}
+		}
+	}
+
+	if args.verbose {
+		eprintln!("merge::read_tweets -> Merged Tweets: {}", merged.len());
+	}
+
+	merged
+		.into_iter()
+		.map(|(value, source_file, source_export)| {
+			let mut object: TweetObject = serde_json::from_value(value).map_err(|error| {
+				io::Error::new(
+					io::ErrorKind::InvalidData,
+					format!("Unable to parse merged Tweet from {source_file} -> {error}"),
+				)
+			})?;
+			object.tweet.source_file = Some(source_file);
+			object.tweet.source_generation_date = source_export.generation_date;
+			object.tweet.source_input_paths = source_export
+				.input_paths
+				.iter()
+				.map(|input_path| input_path.display().to_string())
+				.collect();
+			Ok(object)
+		})
+		.collect()
+}
+
+/// Overwrite fields of `older` with those of `newer`, descending into objects both have, so
+/// fields only `older` holds survive while `null` never replaces a value
+fn merge_value(older: &mut serde_json::Value, newer: serde_json::Value) {
+	match (older, newer) {
+		(serde_json::Value::Object(older), serde_json::Value::Object(newer)) => {
+			for (key, value) in newer {
+				match older.get_mut(&key) {
+					Some(older_value) => merge_value(older_value, value),
+					None => {
+						older.insert(key, value);
+					}
+				}
+			}
+		}
+		(_, serde_json::Value::Null) => {}
+		(older, newer) => *older = newer,
+	}
+}
+
+/// Combine already open packed file parts of one archive into an export
+fn packed_export(group: PackedGroup) -> io::Result<Export> {
+	let (packed_paths, archives): (Vec<path::PathBuf>, Vec<Archive>) =
+		group.parts.into_iter().unzip();
+	let archive = Archive::from_parts(archives.into_iter().flat_map(Archive::into_parts).collect());
+
+	manifest_export(archive, &group.data_manifest, packed_paths)
+}
+
+/// Export of `archive` described by its untyped `data_manifest`
+fn manifest_export(
+	archive: Archive,
+	data_manifest: &serde_json::Value,
+	input_paths: Vec<path::PathBuf>,
+) -> io::Result<Export> {
+	let archive_info: ArchiveInfo = archive::manifest_section(data_manifest, "archiveInfo")?;
+	let user_info: UserInfo = archive::manifest_section(data_manifest, "userInfo")?;
+	let tweet_files = data_manifest
+		.pointer("/dataTypes/tweets/files")
+		.cloned()
+		.map(serde_json::from_value::<Vec<manifest::File>>)
+		.transpose()
+		.map_err(|error| {
+			io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("Unable to parse dataTypes.tweets of data/manifest.js -> {error}"),
+			)
+		})?
+		.unwrap_or_default();
+
+	Ok(Export {
+		archive,
+		generation_date: Some(archive_info.generation_date),
+		tweet_files: file_names(&tweet_files),
+		user_info: Some(user_info),
+		input_paths,
+	})
+}
+
+/// `generationDate` and account of untyped `data_manifest` that parts of one archive share
+fn packed_key(data_manifest: &serde_json::Value) -> io::Result<PackedKey> {
+	let archive_info: ArchiveInfo = archive::manifest_section(data_manifest, "archiveInfo")?;
+	let user_info: UserInfo = archive::manifest_section(data_manifest, "userInfo")?;
+
+	Ok((archive_info.generation_date, user_info.account_id))
+}
+
+/// Name of archive packed file at `packed_path` is part of, eg. `twitter-2023-08-31-abc` for
+/// `twitter-2023-08-31-abc-part2.zip`
+fn archive_name(packed_path: &path::Path) -> String {
+	let file_name = packed_path
+		.file_name()
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_default();
+
+	match archive::split_part_of(&file_name) {
+		Some((base_name, _)) => base_name.to_string(),
+		None => file_name.split('.').next().unwrap_or_default().to_string(),
+	}
+}
+
+/// Names of manifest listed `files`
+fn file_names(files: &[manifest::File]) -> Vec<String> {
+	files.iter().map(|file| file.file_name.clone()).collect()
+}
@@ -0,0 +1,73 @@
+#!/usr/bin/env rust
+
+//! Write Moments, from archive `data/moment.js`, as one collection page per Moment that embeds
+//! its member Tweets
+
+use std::io;
+use twitter_archive::structs::manifest::Manifest;
+
+use crate::arguments::Args;
+use crate::context::Context;
+use crate::media::{self, MediaIndex};
+use crate::output;
+use crate::post_build;
+use crate::structs::MomentObject;
+
+/// Write a page per Moment into `--moments-directory`, if defined, copying cover media into
+/// `--media-directory`
+///
+/// Must be called after posts were indexed, so member Tweets link to their local posts
+pub fn to_markdown(data_manifest: &Manifest, context: &mut Context, args: &Args) -> io::Result<()> {
+	let Some(moments_directory) = &args.moments_directory else {
+		return Ok(());
+	};
+
+	context.directory = Some(moments_directory.clone());
+	let context = &*context;
+	let Some(archive) = &context.archive else {
+		return Ok(());
+	};
+
+	let data_types = &data_manifest.data_types;
+	let data_moments: Vec<MomentObject> = archive.read_data(&data_types.moment.files, args)?;
+	if args.verbose {
+		eprintln!(
+			"moments::to_markdown -> Moments found: {}",
+			data_moments.len()
+		);
+	}
+
+	let moments_directory_path = context.post_directory();
+	output::create_directory(&moments_directory_path, args)?;
+
+	let media_index = match &args.media_directory {
+		Some(_) => MediaIndex::new(archive, &data_types.moments_media.media_directory, args),
+		None => MediaIndex::default(),
+	};
+
+	for moment in data_moments.iter().map(|object| &object.moment) {
+		let mut covers: Vec<String> = vec![];
+		for url in &moment.cover_media_urls {
+			match media_index.find_remote(&moment.moment_id, url) {
+				Some(file_name) if args.media_directory.is_some() => {
+					media::copy(archive, file_name, args)?;
+					covers.push(media::url(file_name, &moments_directory_path, args));
+				}
+				_ => covers.push(url.clone()),
+			}
+		}
+
+		let file_name = match moment.created_at {
+			Some(created_at) => format!("{}-{}.md", created_at.format("%F"), moment.moment_id),
+			None => format!("{}.md", moment.moment_id),
+		};
+
+		output::write_markdown(
+			&moments_directory_path.join(file_name),
+			&post_build::moment(moment, &covers, context, args),
+			args,
+		)?;
+	}
+
+	Ok(())
+}
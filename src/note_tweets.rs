@@ -0,0 +1,185 @@
+#!/usr/bin/env rust
+
+//! Replace truncated `full_text` of long-form Tweets with complete text from `data/note-tweet.js`
+//!
+//! Notes have their own ID, so each is joined to the Tweet it belongs to by Tweet ID, when the
+//! archive provides one, otherwise by creation time and leading text.
+
+use twitter_archive::structs::tweets::{
+	TweetEntitiesEntry, TweetEntitiesUserMention, TweetEntitiesUserUrl,
+};
+
+use crate::arguments::Args;
+use crate::structs::{NoteTweet, NoteTweetObject, TweetObject, TweetStyle, TweetStyleKind};
+
+/// Maximum seconds between note and Tweet creation times for them to be considered a pair
+const CREATED_AT_TOLERANCE_SECONDS: i64 = 5;
+
+/// Number of leading characters of truncated Tweet text that note text must start with
+const PREFIX_LENGTH: usize = 40;
+
+/// Replace text, entities, and styling of each Tweet that a note belongs to
+pub fn apply(data_tweets: &mut [TweetObject], notes: &[NoteTweetObject], args: &Args) {
+	let mut applied = 0;
+
+	for object in notes {
+		let note = &object.note_tweet;
+
+		let Some(index) = find(data_tweets, note) else {
+			eprintln!(
+				"note_tweets::apply -> Skipping note without Tweet of same ID, or leading text -> {}",
+				note.note_tweet_id
+			);
+			continue;
+		};
+
+		replace(&mut data_tweets[index], note);
+		applied += 1;
+	}
+
+	if args.verbose {
+		eprintln!(
+			"note_tweets::apply -> Notes applied: {applied} of {}",
+			notes.len()
+		);
+	}
+}
+
+/// Index of Tweet that `note` belongs to
+fn find(data_tweets: &[TweetObject], note: &NoteTweet) -> Option<usize> {
+	let by_id = data_tweets.iter().position(|object| {
+		let id_str = &object.tweet.id_str;
+		note.tweet_id.as_ref() == Some(id_str) || &note.note_tweet_id == id_str
+	});
+	if by_id.is_some() {
+		return by_id;
+	}
+
+	let candidates = data_tweets
+		.iter()
+		.enumerate()
+		.filter(|(_, object)| {
+			(object.tweet.created_at - note.created_at)
+				.num_seconds()
+				.abs() <= CREATED_AT_TOLERANCE_SECONDS
+		})
+		.collect::<Vec<(usize, &TweetObject)>>();
+
+	// Only Tweet created at about same time is never enough, its text must match too
+	let note_text = note.core.text.trim_start();
+	candidates
+		.iter()
+		.find(|(_, object)| {
+			let prefix = truncated_prefix(&object.tweet.full_text);
+			!prefix.is_empty() && note_text.starts_with(&prefix)
+		})
+		.map(|(index, _)| *index)
+}
+
+/// Leading, un-escaped, characters of truncated Tweet text that precede any ellipsis or link
+fn truncated_prefix(full_text: &str) -> String {
+	let text = full_text
+		.split(['…', '\n'])
+		.next()
+		.unwrap_or_default()
+		.split("http")
+		.next()
+		.unwrap_or_default()
+		.replace("&lt;", "<")
+		.replace("&gt;", ">")
+		.replace("&amp;", "&");
+
+	text.trim().chars().take(PREFIX_LENGTH).collect()
+}
+
+/// Overwrite Tweet text, entities, and styles with those of `note`
+fn replace(object: &mut TweetObject, note: &NoteTweet) {
+	let core = &note.core;
+	let tweet = &mut object.tweet;
+
+	// Tweets are HTML escaped within archives, so notes are too for consistent rendering
+	let full_text = core
+		.text
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;");
+
+	let previous_mentions = tweet.inner.entities.user_mentions.clone();
+	tweet.inner.entities.user_mentions = core
+		.mentions
+		.iter()
+		.map(|mention| {
+			let previous = previous_mentions.iter().find(|previous| {
+				previous
+					.screen_name
+					.eq_ignore_ascii_case(&mention.screen_name)
+			});
+
+			TweetEntitiesUserMention {
+				name: previous
+					.map(|previous| previous.name.clone())
+					.unwrap_or_else(|| mention.screen_name.clone()),
+				screen_name: mention.screen_name.clone(),
+				indices: [mention.from_index, mention.to_index],
+				id_str: previous
+					.map(|previous| previous.id_str.clone())
+					.unwrap_or_default(),
+				id: previous
+					.map(|previous| previous.id.clone())
+					.unwrap_or_default(),
+			}
+		})
+		.collect();
+
+	tweet.inner.entities.urls = core
+		.urls
+		.iter()
+		.map(|url| TweetEntitiesUserUrl {
+			url: url.short_url.clone(),
+			expanded_url: url.expanded_url.clone(),
+			display_url: url.display_url.clone(),
+			indices: [url.from_index, url.to_index],
+		})
+		.collect();
+
+	tweet.inner.entities.hashtags = core
+		.hashtags
+		.iter()
+		.map(|hashtag| TweetEntitiesEntry {
+			text: hashtag.hashtag_value.clone(),
+			indices: [hashtag.from_index, hashtag.to_index],
+		})
+		.collect();
+
+	tweet.inner.entities.symbols = core
+		.cashtags
+		.iter()
+		.map(|cashtag| TweetEntitiesEntry {
+			text: cashtag.cashtag_value.clone(),
+			indices: [cashtag.from_index, cashtag.to_index],
+		})
+		.collect();
+
+	tweet.styles = core
+		.styletags
+		.iter()
+		.flat_map(|styletag| {
+			styletag
+				.style_types
+				.iter()
+				.filter_map(|style_type| match style_type.name.to_lowercase().as_str() {
+					"bold" => Some(TweetStyleKind::Bold),
+					"italic" => Some(TweetStyleKind::Italic),
+					_ => None,
+				})
+				.map(|kind| TweetStyle {
+					kind,
+					indices: [styletag.from_index, styletag.to_index],
+				})
+		})
+		.collect();
+
+	tweet.inner.display_text_range = [0, core.text.chars().count()];
+	tweet.inner.truncated = false;
+	tweet.inner.full_text = full_text;
+}
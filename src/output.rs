@@ -0,0 +1,100 @@
+#!/usr/bin/env rust
+
+//! Write MarkDown files, and their directories, shared by every kind of export
+
+use std::io::Write;
+use std::{fs, io, path};
+
+use crate::arguments::Args;
+use crate::media;
+
+/// Create `directory_path` if none exists, only logs what would be created when `--dry-run` is set
+pub fn create_directory(directory_path: &path::Path, args: &Args) -> io::Result<()> {
+	if directory_path.is_dir() {
+		return Ok(());
+	}
+
+	if args.verbose || args.dry_run {
+		eprintln!("output::create_directory -> {}", directory_path.display());
+	}
+
+	if !args.dry_run {
+		fs::create_dir_all(directory_path)?;
+	}
+
+	Ok(())
+}
+
+/// Default directory for an export that must not mix with posts, eg. `_tweets_likes` when
+/// `--output-directory` is `_tweets` and `suffix` is `likes`
+pub fn sibling_directory(output_directory_path: &path::Path, suffix: &str) -> String {
+	format!(
+		"{}_{suffix}",
+		output_directory_path
+			.display()
+			.to_string()
+			.trim_end_matches('/')
+	)
+}
+
+/// Write `content` to `file_path` unless a file of same name exists, or print it to standard out
+/// when `--dry-run` is set
+pub fn write_markdown(file_path: &path::Path, content: &str, args: &Args) -> io::Result<()> {
+	if file_path.is_file() {
+		eprintln!(
+			"output::write_markdown -> Skipping existing file -> {}",
+			file_path.display()
+		);
+		return Ok(());
+	}
+
+	if args.dry_run {
+		println!("{}", content);
+		return Ok(());
+	}
+
+	let mut output = fs::File::create(file_path)?;
+	write!(output, "{}", content)?;
+	if !args.verbose {
+		eprintln!("Wrote file -> {}", file_path.display());
+	}
+
+	Ok(())
+}
+
+/// Write `content` to `file_path`, replacing any file of same name so data snapshots, eg. graph
+/// CSV, follow newer archives, or print it to standard out when `--dry-run` is set
+pub fn write_data(file_path: &path::Path, content: &str, args: &Args) -> io::Result<()> {
+	if args.dry_run {
+		println!("{}", content);
+		return Ok(());
+	}
+
+	let mut output = fs::File::create(file_path)?;
+	write!(output, "{}", content)?;
+	if !args.verbose {
+		eprintln!("Wrote file -> {}", file_path.display());
+	}
+
+	Ok(())
+}
+
+/// Whether `path` is, or is found beneath, `directory`
+pub fn is_within(path: &path::Path, directory: &path::Path) -> bool {
+	media::normalize(path).starts_with(&media::normalize(directory))
+}
+
+/// Published directory, `--output-directory` or `--media-directory`, that `path` would be found
+/// within, so private exports may refuse to write there
+pub fn public_directory_of(
+	path: &path::Path,
+	output_directory_path: &path::Path,
+	args: &Args,
+) -> Option<path::PathBuf> {
+	let mut public_directories = vec![output_directory_path.to_path_buf()];
+	public_directories.extend(args.media_directory.iter().map(path::PathBuf::from));
+
+	public_directories
+		.into_iter()
+		.find(|public_directory| is_within(path, public_directory))
+}
@@ -1,13 +1,18 @@
 #!/usr/bin/env rust
 
-use crate::arguments::Args;
-use twitter_archive::structs::tweets::{Tweet, TweetEntitiesUserMention};
+use crate::arguments::{Args, EditPolicy};
+use crate::context::Context;
+use crate::structs::{Moment, Tweet, TweetMedia, TweetStyleKind};
+use twitter_archive::structs::tweets::{
+	TweetEntitiesEntry, TweetEntitiesUserMention, TweetEntitiesUserUrl,
+};
 
 /// Combine results of `front_matter` and `content` functions
-pub fn post(tweet: &Tweet, args: &Args) -> String {
-	let front_matter = format!("---\n{}\n---", front_matter(tweet, args));
-
-	let content = content(tweet, args);
+///
+/// Retweets are written by `retweet_link` instead when `--retweets link` is set
+pub fn post(tweet: &Tweet, context: &Context, args: &Args) -> String {
+	let front_matter = format!("---\n{}\n---", front_matter(tweet, context, args));
+	let content = content_and_edit_history(tweet, context, args);
 
 	if args.verbose {
 		eprintln!(
@@ -24,6 +29,279 @@ pub fn post(tweet: &Tweet, args: &Args) -> String {
 	format!("{}\n\n{}", front_matter, content)
 }
 
+/// Combine results of `front_matter`, for first Tweet, and `content` of every Tweet in thread
+///
+/// Each Tweet is preceded by an anchor named after its ID, and separated by horizontal rules
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ---
+/// layout: post
+/// date: "2023-08-30 22:20:03 +0000"
+/// twitter:
+///   post: https://twitter.com/i/web/status/1697011324369178968
+///   thread:
+///     - id: "1697011324369178968"
+///       post: https://twitter.com/i/web/status/1697011324369178968
+///     - id: "1697011582767661398"
+///       post: https://twitter.com/i/web/status/1697011582767661398
+/// ---
+///
+/// <a id="1697011324369178968"></a>
+///
+/// First Tweet of thread
+///
+/// ---
+///
+/// <a id="1697011582767661398"></a>
+///
+/// Second Tweet of thread
+/// ```
+pub fn thread(tweets: &[&Tweet], context: &Context, args: &Args) -> String {
+	let front_matter = format!(
+		"---\n{}\n{}\n---",
+		front_matter(tweets[0], context, args),
+		front_matter_thread(tweets, args)
+	);
+
+	let content = tweets
+		.iter()
+		.map(|tweet| {
+			format!(
+				"<a id=\"{}\"></a>\n\n{}",
+				tweet.id_str,
+				content_and_edit_history(tweet, context, args)
+			)
+		})
+		.collect::<Vec<String>>()
+		.join("\n\n---\n\n");
+
+	if args.verbose {
+		eprintln!(
+			"post_build::thread::front_matter vvv\n{}\npost_build::thread::front_matter ^^^",
+			front_matter
+		);
+	}
+
+	format!("{}\n\n{}", front_matter, content)
+}
+
+/// Stub for deleted Tweets, listing only IDs and dates, written instead of `post` or `thread`
+/// when `--deleted-tweets tombstone` is set
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ---
+/// layout: deleted_tweet
+/// date: "2023-08-30 22:20:03 +0000"
+/// deleted_at: "2023-08-30 23:20:03 +0000"
+/// twitter:
+///   id: "1697011324369178968"
+/// ---
+/// ```
+pub fn tombstone(tweets: &[&Tweet], args: &Args) -> String {
+	let tweet = tweets[0];
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(format!("layout: {}", args.deleted_post_layout));
+	lines.push(front_matter_date(tweet, args));
+
+	if let Some(deleted_at) = front_matter_deleted_at(tweet, args) {
+		lines.push(deleted_at);
+	}
+
+	lines.push(format!("{}:", args.post_twitter_key));
+	lines.push(format!("  id: {:?}", tweet.id_str));
+
+	if tweets.len() > 1 {
+		lines.push("  thread:".into());
+		for tweet in tweets {
+			lines.push(format!("    - id: {:?}", tweet.id_str));
+		}
+	}
+
+	let result = format!("---\n{}\n---\n", lines.join("\n"));
+	if args.verbose {
+		eprintln!("post_build::tombstone -> {result}");
+	}
+
+	result
+}
+
+/// Minimal post for old-style retweet, only linking to retweeted Tweet, written instead of `post`
+/// when `--retweets link` is set
+///
+/// Retweeted Tweet is linked when its ID is found within media or links of retweet, otherwise
+/// link of retweet itself is used, which Twitter redirects to retweeted Tweet.
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ---
+/// layout: post
+/// date: "2023-08-30 22:20:03 +0000"
+/// twitter:
+///   post: https://twitter.com/i/web/status/1697011324369178968
+///   retweet_of:
+///     account: https://twitter.com/SomeoneElse
+///     post: https://twitter.com/i/web/status/1697000000000000000
+///     retweet_url: https://twitter.com/i/web/status/1697011324369178968
+/// ---
+///
+/// [Retweet of @SomeoneElse](https://twitter.com/i/web/status/1697000000000000000)
+/// ```
+pub fn retweet_link(tweet: &Tweet, args: &Args) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(format!("layout: {}", args.post_layout));
+	lines.push(front_matter_date(tweet, args));
+
+	if let Some(author) = &args.post_author {
+		lines.push(format!("author: {}", author));
+	}
+
+	lines.push(format!("{}:", args.post_twitter_key));
+	lines.push(format!(
+		"  post: {}",
+		twitter_url_status(&tweet.id_str, args)
+	));
+	lines.extend(front_matter_retweet_of(tweet, args));
+
+	let content = match tweet.retweet_of() {
+		Some((screen_name, _)) => format!(
+			"\n[Retweet of @{}]({})\n",
+			screen_name,
+			twitter_url_status(
+				&tweet.retweeted_id_str().unwrap_or(tweet.id_str.clone()),
+				args
+			)
+		),
+		None => String::new(),
+	};
+
+	let result = format!("---\n{}\n---\n{content}", lines.join("\n"));
+	if args.verbose {
+		eprintln!("post_build::retweet_link -> {result}");
+	}
+
+	result
+}
+
+/// Collection page for Moment, embedding each member Tweet in curated order
+///
+/// Member Tweets found within archive are quoted and link to their posts, others only link to
+/// Twitter
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ---
+/// layout: post
+/// date: "2018-03-01 12:00:00 +0000"
+/// title: "Tips worth keeping"
+/// description: "Collection of favourite technical tips"
+/// twitter:
+///   moment: https://twitter.com/i/moments/1000000000000000001
+///   cover_media:
+///     - ../assets/tweets/1000000000000000001-DXyZaBcDeFg.jpg
+///   posts:
+///     - id: "1697011324369178968"
+///       post: ../2023-08-30-1697011324369178968.md
+/// ---
+///
+/// ![Tips worth keeping](../assets/tweets/1000000000000000001-DXyZaBcDeFg.jpg)
+///
+/// Collection of favourite technical tips
+///
+/// <a id="1697011324369178968"></a>
+///
+/// > [2023-08-30 22:20:03 +0000](../2023-08-30-1697011324369178968.md)
+/// >
+/// > Tweet text
+/// ```
+pub fn moment(moment: &Moment, covers: &[String], context: &Context, args: &Args) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(format!("layout: {}", args.post_layout));
+	if let Some(created_at) = moment.created_at {
+		lines.push(format!(
+			"date: {:?}",
+			created_at.format(&args.post_date_format).to_string()
+		));
+	}
+	lines.push(format!("title: {:?}", moment.title));
+	if let Some(description) = &moment.description {
+		lines.push(format!("description: {:?}", description));
+	}
+	if let Some(author) = &args.post_author {
+		lines.push(format!("author: {}", author));
+	}
+
+	lines.push(format!("{}:", args.post_twitter_key));
+	lines.push(format!(
+		"  moment: {}",
+		twitter_url_moment(&moment.moment_id, args)
+	));
+	if !covers.is_empty() {
+		lines.push("  cover_media:".into());
+		covers.iter().for_each(|cover| {
+			lines.push(format!("    - {}", cover));
+		});
+	}
+	if !moment.tweets.is_empty() {
+		lines.push("  posts:".into());
+		for id_str in &moment.tweets {
+			lines.push(format!("    - id: {:?}", id_str));
+			lines.push(format!(
+				"      post: {}",
+				link_status(id_str, context, args)
+			));
+		}
+	}
+
+	let mut blocks: Vec<String> = vec![];
+	covers.iter().for_each(|cover| {
+		blocks.push(format!(
+			"![{}]({})",
+			markdown_escape_alt(&moment.title),
+			cover
+		));
+	});
+	if let Some(description) = moment.description.as_ref().filter(|d| !d.trim().is_empty()) {
+		blocks.push(description.trim().to_string());
+	}
+	for id_str in &moment.tweets {
+		let embed = match context.tweets.get(id_str) {
+			Some(_) => content_quote(
+				&QuoteOf {
+					id_str: id_str.clone(),
+					url: None,
+				},
+				context,
+				args,
+			),
+			None => {
+				let url = twitter_url_status(id_str, args);
+				markdown_blockquote(&format!("[{}]({})", url, url))
+			}
+		};
+		blocks.push(format!("<a id=\"{}\"></a>\n\n{}", id_str, embed));
+	}
+
+	let result = format!(
+		"---\n{}\n---\n\n{}\n",
+		lines.join("\n"),
+		blocks.join("\n\n")
+	);
+	if args.verbose {
+		eprintln!("post_build::moment -> {result}");
+	}
+
+	result
+}
+
 /// FrontMatter from Twitter archive Tweets
 ///
 /// ## Example output YAML (FrontMatter)
@@ -42,8 +320,13 @@ pub fn post(tweet: &Tweet, args: &Args) -> String {
 ///     - https://twitter.com/l422y
 ///   urls:
 ///     - https://developer.mozilla.org/en-US/docs/Web/API/History_API
+///   media:
+///     - ../assets/tweets/1346551404433977352-ErCfa0bXcAEkIv_.jpg
 /// ```
 ///
+/// > Note; `media` is only listed when `--media-directory` is defined and archive includes
+/// > copies of attached images
+///
 /// ## Example input JSON
 ///
 /// ```json
@@ -106,12 +389,20 @@ pub fn post(tweet: &Tweet, args: &Args) -> String {
 ///   }
 /// ]
 /// ```
-pub fn front_matter(tweet: &Tweet, args: &Args) -> String {
+pub fn front_matter(tweet: &Tweet, context: &Context, args: &Args) -> String {
 	let mut lines: Vec<String> = vec![];
 
-	lines.push(format!("layout: {}", args.post_layout));
+	let layout = match tweet.deleted_at {
+		Some(_) => &args.deleted_post_layout,
+		None => &args.post_layout,
+	};
+	lines.push(format!("layout: {}", layout));
 	lines.push(front_matter_date(tweet, args));
 
+	if let Some(deleted_at) = front_matter_deleted_at(tweet, args) {
+		lines.push(deleted_at);
+	}
+
 	if let Some(author) = &args.post_author {
 		lines.push(format!("author: {}", author));
 	}
@@ -121,7 +412,32 @@ pub fn front_matter(tweet: &Tweet, args: &Args) -> String {
 		lines.push(format!("tags: [{}]", tags));
 	}
 
-	lines.push(front_matter_links(tweet, args));
+	lines.push(front_matter_links(tweet, context, args));
+
+	let community = front_matter_community(tweet, args);
+	if !community.is_empty() {
+		lines.push(community);
+	}
+
+	let media = front_matter_media(tweet, context, args);
+	if !media.is_empty() {
+		lines.push(media);
+	}
+
+	let videos = front_matter_videos(tweet, context, args);
+	if !videos.is_empty() {
+		lines.push(videos);
+	}
+
+	let edits = front_matter_edits(tweet, context, args);
+	if !edits.is_empty() {
+		lines.push(edits);
+	}
+
+	let provenance = front_matter_provenance(tweet, context, args);
+	if !provenance.is_empty() {
+		lines.push(provenance);
+	}
 
 	// Put it all together and ship it!
 	let result = lines.join("\n");
@@ -144,18 +460,29 @@ pub fn file_name(tweet: &Tweet, args: &Args) -> String {
 	file_name
 }
 
+/// Subdirectory of output directory that post starting with `tweet` is written within
+pub fn directory(tweet: &Tweet, args: &Args) -> Option<String> {
+	match tweet.community_tweet {
+		true => args.community_directory.clone(),
+		false => None,
+	}
+}
+
 /// Build mostly MarkDown compatible string from `.tweets[].tweet.full_text` and attempt to inject
-/// links to mentioned users as well as any shared links by parsing;
+/// links to mentioned users, hashtags, symbols, shared links, and media by parsing;
 ///
-/// - `.tweets[].tweet.entries.user_mentions[]`
-/// - `.tweets[].tweet.entries.urls[]`
+/// - `.tweets[].tweet.entities.user_mentions[]`
+/// - `.tweets[].tweet.entities.urls[]`
+/// - `.tweets[].tweet.entities.hashtags[]`
+/// - `.tweets[].tweet.entities.symbols[]`
+/// - `.tweets[].tweet.extended_entities.media[]`
 ///
 /// ## Example output MarkDown
 ///
 /// ```markdown
-/// @__akash__19 [@L422Y](https://twitter.com/l422y) It may be possible to edit history from #JavaScript, check MDN documentation for details...
+/// @__akash__19 [@L422Y](https://twitter.com/l422y) It may be possible to edit history from [#JavaScript](https://twitter.com/hashtag/JavaScript), check MDN documentation for details...
 ///
-/// [https://t.co/7I1G7nykqo](https://developer.mozilla.org/en-US/docs/Web/API/History_API)
+/// [developer.mozilla.org/en-US/docs/Web…](https://developer.mozilla.org/en-US/docs/Web/API/History_API)
 ///
 /// ... TLDR, here's an untested example...
 ///
@@ -227,55 +554,621 @@ pub fn file_name(tweet: &Tweet, args: &Args) -> String {
 ///   }
 /// ]
 /// ```
-pub fn content(tweet: &Tweet, args: &Args) -> String {
-	if tweet.entities.user_mentions.is_empty() {
-		return tweet.full_text.clone();
+pub fn content(tweet: &Tweet, context: &Context, args: &Args) -> String {
+	content_with_quote(tweet, true, context, args)
+}
+
+/// Same as `content` with collapsible edit history appended when `--edits history` is set
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// Latest version of Tweet
+///
+/// <details markdown="1">
+/// <summary>Edit history</summary>
+///
+/// **2023-08-12 16:10:37 +0000**
+///
+/// First version of Tweet
+///
+/// </details>
+/// ```
+fn content_and_edit_history(tweet: &Tweet, context: &Context, args: &Args) -> String {
+	let result = content(tweet, context, args);
+
+	if args.edits != EditPolicy::History {
+		return result;
+	}
+
+	let Some(versions) = context.edits.get(&tweet.id_str) else {
+		return result;
+	};
+
+	let history = versions
+		.iter()
+		.map(|version| {
+			format!(
+				"**{}**\n\n{}",
+				version.created_at.format(&args.post_date_format),
+				content(version, context, args).trim_end()
+			)
+		})
+		.collect::<Vec<String>>()
+		.join("\n\n");
+
+	format!(
+		"{}\n\n<details markdown=\"1\">\n<summary>Edit history</summary>\n\n{}\n\n</details>",
+		result.trim_end(),
+		history
+	)
+}
+
+/// Same as `content` though quoted Tweets are only rendered inline when `quote` is `true`, which
+/// prevents quotes of quotes from nesting indefinitely
+fn content_with_quote(tweet: &Tweet, quote: bool, context: &Context, args: &Args) -> String {
+	let text = &tweet.full_text;
+	let spans = content_spans(tweet, args);
+	let quote_of = match quote {
+		true => QuoteOf::new(tweet),
+		false => None,
+	};
+
+	let retweet_of = tweet.retweet_of();
+
+	let markers = content_styles(tweet, &spans);
+	let mut markers = markers.iter().peekable();
+
+	let mut result = String::with_capacity(text.len());
+	let mut cursor = retweet_of.map(|(_, prefix_len)| prefix_len).unwrap_or(0);
+	let mut push_text = |result: &mut String, start: usize, end: usize| {
+		let mut position = start;
+		while let Some(marker) = markers.next_if(|marker| marker.offset <= end) {
+			let offset = marker.offset.max(position);
+			result.push_str(&text[position..offset]);
+			result.push_str(marker.markdown);
+			position = offset;
+		}
+		result.push_str(&text[position..end]);
+	};
+
+	for span in spans {
+		if span.start < cursor {
+			continue;
+		}
+
+		push_text(&mut result, cursor, span.start);
+		match (&span.kind, &quote_of) {
+			(SpanKind::Url(url), Some(quote_of)) if quote_of.is_trailing_url(url) => {}
+			_ => {
+				result.push_str(&span.markdown(&text[span.start..span.end], tweet, context, args));
+			}
+		}
+		cursor = span.end;
+	}
+	push_text(&mut result, cursor, text.len());
+
+	if let Some((screen_name, _)) = retweet_of {
+		result = format!(
+			"Retweet of [@{}]({})\n\n{}",
+			screen_name,
+			twitter_url_account(screen_name, args),
+			markdown_blockquote(&result)
+		);
+	}
+
+	if let Some(quote_of) = &quote_of {
+		result = format!(
+			"{}\n\n{}",
+			result.trim_end(),
+			content_quote(quote_of, context, args)
+		);
 	}
 
-	let mut user_mentions = tweet.entities.user_mentions.clone();
-	let mut urls = tweet.entities.urls.clone();
-	let mut characters: Vec<String> = vec![];
-	for (index, character) in tweet.full_text.clone().chars().enumerate() {
-		if user_mentions.is_empty() && urls.is_empty() {
-			characters.push(character.to_string());
+	let embeds = content_media(tweet, context, args);
+	if !embeds.is_empty() {
+		result = format!("{}\n\n{}", result.trim_end(), embeds);
+	}
+
+	result
+}
+
+/// MarkDown emphasis delimiter to insert at byte `offset` of `full_text`
+struct StyleMarker {
+	/// Byte offset within `full_text`
+	offset: usize,
+
+	/// Delimiter to insert, eg. `**` for bold
+	markdown: &'static str,
+}
+
+/// Convert `styles` of long-form Tweets into sorted emphasis delimiters
+///
+/// Whitespace is trimmed from within each styled range, because MarkDown will not recognize
+/// `** bold **`, and delimiters that would land inside an entity are moved to its edges so links
+/// remain intact.  Closing delimiters sort before opening ones at the same offset, and nested
+/// ranges close in reverse of the order they were opened.
+fn content_styles(tweet: &Tweet, spans: &[Span]) -> Vec<StyleMarker> {
+	let text = &tweet.full_text;
+	let offsets = text_offsets(text, false);
+
+	let mut markers: Vec<(usize, usize, usize, StyleMarker)> = vec![];
+	for style in &tweet.styles {
+		let (Some(&start), Some(&end)) =
+			(offsets.get(style.indices[0]), offsets.get(style.indices[1]))
+		else {
+			continue;
+		};
+
+		let styled = &text[start..end.max(start)];
+		let start = start + (styled.len() - styled.trim_start().len());
+		let end = start + styled.trim().len();
+		if start >= end {
 			continue;
 		}
 
-		// Yes these two loops be kinda ugly, but it works well enough
-		for user_mention in user_mentions.clone() {
-			if user_mention.indices[0] == index {
-				characters.push("[".into());
-			} else if user_mention.indices[1] == index {
-				characters.push("]".into());
+		let start = spans
+			.iter()
+			.find(|span| span.start < start && start < span.end)
+			.map(|span| span.start)
+			.unwrap_or(start);
+		let end = spans
+			.iter()
+			.find(|span| span.start < end && end < span.end)
+			.map(|span| span.end)
+			.unwrap_or(end);
+
+		let markdown = match style.kind {
+			TweetStyleKind::Bold => "**",
+			TweetStyleKind::Italic => "*",
+		};
+
+		markers.push((
+			start,
+			1,
+			usize::MAX - end,
+			StyleMarker {
+				offset: start,
+				markdown,
+			},
+		));
+		markers.push((
+			end,
+			0,
+			usize::MAX - start,
+			StyleMarker {
+				offset: end,
+				markdown,
+			},
+		));
+	}
+
+	markers.sort_by_key(|(offset, order, length, _)| (*offset, *order, *length));
+	markers
+		.into_iter()
+		.map(|(_, _, _, marker)| marker)
+		.collect()
+}
+
+/// Quoted Tweet ID and trailing `.tweets[].tweet.entities.urls[]` entry that links to it
+struct QuoteOf<'a> {
+	/// ID of quoted Tweet
+	id_str: String,
 
-				let link = format!("({})", twitter_url_account(&user_mention.screen_name, args));
-				link.chars().for_each(|c| characters.push(c.into()));
+	/// Link to quoted Tweet found at end of `full_text`, if any
+	url: Option<&'a TweetEntitiesUserUrl>,
+}
+
+impl<'a> QuoteOf<'a> {
+	/// Detect quoted Tweet via `quoted_status_id_str`, or last URL of `full_text` when that URL
+	/// points at a Tweet
+	fn new(tweet: &'a Tweet) -> Option<Self> {
+		let text = tweet.full_text.trim_end();
+		let trailing_url = tweet
+			.entities
+			.urls
+			.iter()
+			.filter(|url| twitter_status_id(&url.expanded_url).is_some())
+			.find(|url| text.ends_with(&url.url));
 
-				// Reduce the size of `user_mentions` for next pass of outer for loop
-				user_mentions.rotate_left(1);
-				user_mentions.pop();
+		match &tweet.quoted_status_id_str {
+			Some(id_str) => Some(Self {
+				id_str: id_str.clone(),
+				url: trailing_url
+					.filter(|url| twitter_status_id(&url.expanded_url) == Some(id_str.as_str())),
+			}),
+			None => {
+				let url = trailing_url?;
+				Some(Self {
+					id_str: twitter_status_id(&url.expanded_url)?.to_string(),
+					url: Some(url),
+				})
 			}
 		}
+	}
 
-		for url in urls.clone() {
-			if url.indices[0] == index {
-				characters.push("[".into());
-			} else if url.indices[1] == index {
-				characters.push("]".into());
+	/// Returns `true` if `url` is the trailing link to quoted Tweet
+	fn is_trailing_url(&self, url: &TweetEntitiesUserUrl) -> bool {
+		self.url.is_some_and(|quote_url| quote_url.url == url.url)
+	}
+}
 
-				let link = format!("({})", url.expanded_url);
-				link.chars().for_each(|c| characters.push(c.into()));
+/// Render quoted Tweet as MarkDown blockquote when it is archived, otherwise quote a link to it
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// > [@S0AndS0](https://twitter.com/S0AndS0) -- [2023-08-30 22:20:03 +0000](2023-08-30-1697011324369178968.md)
+/// >
+/// > Anyone else occasionally annoyed we're not allowed to modify visibility of `details` HTML element children via `:target` ID with CSS only?
+/// ```
+fn content_quote(quote_of: &QuoteOf, context: &Context, args: &Args) -> String {
+	let remote_url = match quote_of.url {
+		Some(url) => url.expanded_url.clone(),
+		None => twitter_url_status(&quote_of.id_str, args),
+	};
+
+	let Some(quoted) = context.tweets.get(&quote_of.id_str) else {
+		return format!("> **Quoted Tweet:** [{}]({})", remote_url, remote_url);
+	};
+
+	let author = remote_url
+		.split('/')
+		.nth(3)
+		.filter(|name| !name.is_empty() && *name != "i")
+		.map(String::from)
+		.or_else(|| args.post_author.clone());
+
+	let date = quoted.created_at.format(&args.post_date_format).to_string();
+
+	let mut heading = format!("[{}]({})", date, link_status(&quoted.id_str, context, args));
+	if let Some(author) = author {
+		heading = format!(
+			"[@{}]({}) -- {}",
+			author,
+			twitter_url_account(&author, args),
+			heading
+		);
+	}
 
-				// Reduce the size of `urls` for next pass of outer for loop
-				urls.rotate_left(1);
-				urls.pop();
+	let body = content_with_quote(quoted, false, context, args);
+
+	markdown_blockquote(&format!("{}\n\n{}", heading, body))
+}
+
+/// Build MarkDown image, or HTML5 video, embeds for each media file copied from archive
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ![Screenshot of MDN History API page](../assets/tweets/1346551404433977352-ErCfa0bXcAEkIv_.jpg)
+///
+/// <video controls preload="metadata" poster="https://pbs.twimg.com/ext_tw_video_thumb/1697011310355959808/pu/img/IjKl.jpg">
+///   <source src="../assets/tweets/1697011324369178968-AbCd.mp4" type="video/mp4">
+/// </video>
+/// ```
+fn content_media(tweet: &Tweet, context: &Context, args: &Args) -> String {
+	tweet
+		.media()
+		.iter()
+		.filter_map(|media| {
+			let src = context.media_src(&tweet.id_str, media, args)?;
+
+			if media.is_video() {
+				return Some(content_video(tweet, media, &src, context, args));
+			}
+
+			let alt = media
+				.ext_alt_text
+				.clone()
+				.unwrap_or_else(|| media.media_type.clone());
+
+			Some(format!("![{}]({})", markdown_escape_alt(&alt), src))
+		})
+		.collect::<Vec<String>>()
+		.join("\n\n")
+}
+
+/// Build HTML5 video element, `animated_gif` media loop silently and play automatically
+fn content_video(
+	tweet: &Tweet,
+	media: &TweetMedia,
+	src: &str,
+	context: &Context,
+	args: &Args,
+) -> String {
+	let mut attributes: Vec<String> = vec![];
+
+	if media.is_animated_gif() {
+		attributes.push("autoplay loop muted playsinline".into());
+	} else {
+		attributes.push("controls preload=\"metadata\"".into());
+	}
+
+	attributes.push(format!(
+		"poster=\"{}\"",
+		html_escape_attribute(&context.media_poster(&tweet.id_str, media, args))
+	));
+
+	if let Some(alt) = &media.ext_alt_text {
+		attributes.push(format!("aria-label=\"{}\"", html_escape_attribute(alt)));
+	}
+
+	format!(
+		"<video {}>\n  <source src=\"{}\" type=\"video/mp4\">\n</video>",
+		attributes.join(" "),
+		html_escape_attribute(src)
+	)
+}
+
+/// Entity types that may be rendered as MarkDown within Tweet content
+#[derive(Debug)]
+enum SpanKind<'a> {
+	/// Entry of `.tweets[].tweet.entities.user_mentions[]`
+	UserMention(&'a TweetEntitiesUserMention),
+
+	/// Entry of `.tweets[].tweet.entities.urls[]`
+	Url(&'a TweetEntitiesUserUrl),
+
+	/// Entry of `.tweets[].tweet.entities.hashtags[]`
+	Hashtag(&'a TweetEntitiesEntry),
+
+	/// Entry of `.tweets[].tweet.entities.symbols[]`
+	Symbol(&'a TweetEntitiesEntry),
+
+	/// Entry of `.tweets[].tweet.extended_entities.media[]`
+	Media(&'a TweetMedia),
+}
+
+/// Byte range within `full_text` that some entity covers
+#[derive(Debug)]
+struct Span<'a> {
+	/// Inclusive byte offset into `full_text`
+	start: usize,
+
+	/// Exclusive byte offset into `full_text`
+	end: usize,
+
+	/// Entity found between `start` and `end`
+	kind: SpanKind<'a>,
+}
+
+impl Span<'_> {
+	/// Text that entity is expected to cover, used to verify and/or search for span position
+	fn needle(&self) -> String {
+		match self.kind {
+			SpanKind::UserMention(user_mention) => user_mention.screen_name.clone(),
+			SpanKind::Url(url) => url.url.clone(),
+			SpanKind::Hashtag(entry) | SpanKind::Symbol(entry) => entry.text.clone(),
+			SpanKind::Media(media) => media.url.clone(),
+		}
+	}
+
+	/// Returns `true` if `slice` of `full_text` is what this entity is expected to cover
+	fn matches(&self, slice: &str) -> bool {
+		let needle = self.needle();
+		match self.kind {
+			SpanKind::Url(_) | SpanKind::Media(_) => slice == needle,
+			_ => {
+				let mut characters = slice.chars();
+				match characters.next() {
+					Some(sigil) if self.is_sigil(sigil) => {
+						characters.as_str().to_lowercase() == needle.to_lowercase()
+					}
+					_ => false,
+				}
+			}
+		}
+	}
+
+	/// Returns `true` if `character` may prefix entity, including full-width variants
+	fn is_sigil(&self, character: char) -> bool {
+		match self.kind {
+			SpanKind::UserMention(_) => character == '@' || character == '＠',
+			SpanKind::Hashtag(_) => character == '#' || character == '＃',
+			SpanKind::Symbol(_) => character == '$' || character == '＄',
+			_ => false,
+		}
+	}
+
+	/// Render `slice` of `full_text` as MarkDown link for this span
+	///
+	/// Media that will be embedded renders as empty string
+	fn markdown(&self, slice: &str, tweet: &Tweet, context: &Context, args: &Args) -> String {
+		match self.kind {
+			SpanKind::UserMention(user_mention) => format!(
+				"[{slice}]({})",
+				context
+					.self_mention_url(&user_mention.screen_name, args)
+					.unwrap_or_else(|| twitter_url_account(&user_mention.screen_name, args))
+			),
+			SpanKind::Url(url) => format!(
+				"[{}]({})",
+				url.display_url,
+				link_url(&url.expanded_url, context, args)
+			),
+			SpanKind::Hashtag(entry) => {
+				format!("[{slice}]({})", twitter_url_hashtag(&entry.text, args))
+			}
+			SpanKind::Symbol(entry) => {
+				format!("[{slice}]({})", twitter_url_symbol(&entry.text, args))
+			}
+			SpanKind::Media(media) => match context.media_src(&tweet.id_str, media, args) {
+				Some(_) => String::new(),
+				None => format!("[{}]({})", media.display_url, media.expanded_url),
+			},
+		}
+	}
+}
+
+/// Collect every entity of Tweet into sorted, non-overlapping, byte ranges of `full_text`
+///
+/// Entity `indices` count Unicode code points of un-escaped text, however `full_text` is HTML
+/// escaped (`&amp;`, `&lt;`, `&gt;`) and some archives count astral-plane characters (eg. emoji)
+/// as two UTF-16 units.  So each entity is first checked at code point position, then UTF-16
+/// position, and finally searched for nearest match to expected position.
+fn content_spans<'a>(tweet: &'a Tweet, args: &Args) -> Vec<Span<'a>> {
+	let text = &tweet.full_text;
+	let entities = &tweet.entities;
+
+	let mut kinds: Vec<(SpanKind, [usize; 2])> = vec![];
+	entities
+		.user_mentions
+		.iter()
+		.for_each(|e| kinds.push((SpanKind::UserMention(e), e.indices)));
+	entities
+		.urls
+		.iter()
+		.for_each(|e| kinds.push((SpanKind::Url(e), e.indices)));
+	entities
+		.hashtags
+		.iter()
+		.for_each(|e| kinds.push((SpanKind::Hashtag(e), e.indices)));
+	entities
+		.symbols
+		.iter()
+		.for_each(|e| kinds.push((SpanKind::Symbol(e), e.indices)));
+	tweet
+		.media()
+		.iter()
+		.for_each(|e| kinds.push((SpanKind::Media(e), e.indices)));
+
+	let offsets_code_points = text_offsets(text, false);
+	let offsets_utf16 = text_offsets(text, true);
+
+	let mut spans: Vec<Span> = vec![];
+	for (kind, indices) in kinds {
+		let mut span = Span {
+			start: 0,
+			end: 0,
+			kind,
+		};
+
+		let candidates = [&offsets_code_points, &offsets_utf16]
+			.iter()
+			.filter_map(|offsets| Some((*offsets.get(indices[0])?, *offsets.get(indices[1])?)))
+			.collect::<Vec<(usize, usize)>>();
+
+		let found = candidates
+			.iter()
+			.find(|(start, end)| start < end && span.matches(&text[*start..*end]))
+			.cloned()
+			.or_else(|| {
+				let expected = candidates.first().map(|(start, _)| *start).unwrap_or(0);
+				search_span(text, &span, expected, &spans)
+			});
+
+		match found {
+			Some((start, end)) => {
+				span.start = start;
+				span.end = end;
+				spans.push(span);
+			}
+			None => {
+				if args.verbose {
+					eprintln!(
+						"post_build::content_spans -> Unable to locate entity -> {:?}",
+						span.kind
+					);
+				}
 			}
 		}
+	}
+
+	spans.sort_by_key(|span| (span.start, span.end));
 
-		characters.push(character.to_string());
+	let mut result: Vec<Span> = vec![];
+	for span in spans {
+		if let Some(previous) = result.last() {
+			if span.start < previous.end {
+				if args.verbose {
+					eprintln!(
+						"post_build::content_spans -> Skipping overlapping entity -> {:?}",
+						span.kind
+					);
+				}
+				continue;
+			}
+		}
+		result.push(span);
 	}
 
-	characters.join("")
+	result
+}
+
+/// Find nearest occurrence of span needle, to `expected` byte offset, that does not overlap
+/// already claimed spans
+fn search_span(
+	text: &str,
+	span: &Span,
+	expected: usize,
+	claimed: &[Span],
+) -> Option<(usize, usize)> {
+	let needle = span.needle().to_ascii_lowercase();
+	if needle.is_empty() {
+		return None;
+	}
+
+	// ASCII only lower-casing preserves byte offsets
+	let haystack = text.to_ascii_lowercase();
+
+	haystack
+		.match_indices(&needle)
+		.filter_map(|(index, _)| {
+			let end = index + needle.len();
+			let start = match span.kind {
+				SpanKind::Url(_) | SpanKind::Media(_) => index,
+				_ => {
+					let sigil = text[..index].chars().next_back()?;
+					if !span.is_sigil(sigil) {
+						return None;
+					}
+					index - sigil.len_utf8()
+				}
+			};
+
+			let overlaps = claimed
+				.iter()
+				.any(|other| start < other.end && other.start < end);
+			if overlaps {
+				return None;
+			}
+
+			Some((start, end))
+		})
+		.min_by_key(|(start, _)| start.abs_diff(expected))
+}
+
+/// Map logical character index, as used by entity `indices`, to byte offset within `text`
+///
+/// HTML escaped `&amp;`, `&lt;`, and `&gt;` count as one character, and when `utf16` is `true`
+/// characters outside the Basic Multilingual Plane count as two.  Returned list has one extra
+/// element pointing at end of `text`.
+fn text_offsets(text: &str, utf16: bool) -> Vec<usize> {
+	let mut offsets: Vec<usize> = Vec::with_capacity(text.len() + 1);
+
+	let mut index = 0;
+	while index < text.len() {
+		offsets.push(index);
+
+		let rest = &text[index..];
+		if let Some(escaped) = ["&amp;", "&lt;", "&gt;"]
+			.iter()
+			.find(|escaped| rest.starts_with(**escaped))
+		{
+			index += escaped.len();
+			continue;
+		}
+
+		let character = rest.chars().next().unwrap();
+		if utf16 && character.len_utf16() > 1 {
+			offsets.push(index);
+		}
+		index += character.len_utf8();
+	}
+	offsets.push(text.len());
+
+	offsets
 }
 
 /// Re-format Tweet `created_at` with CLI provided format string
@@ -288,6 +1181,128 @@ fn front_matter_date(tweet: &Tweet, args: &Args) -> String {
 	)
 }
 
+/// Re-format Tweet `deleted_at`, if any, with CLI provided format string
+fn front_matter_deleted_at(tweet: &Tweet, args: &Args) -> Option<String> {
+	let deleted_at = tweet.deleted_at?;
+
+	Some(format!(
+		"deleted_at: {:?}",
+		deleted_at.format(&args.post_date_format).to_string()
+	))
+}
+
+/// List X Community that Tweet was posted within into YAML compatible syntax
+///
+/// ## Example output YAML
+///
+/// ```yaml
+///   community:
+///     id: "1498728462582538240"
+///     name: "Rust Programming"
+///     url: https://twitter.com/i/communities/1498728462582538240
+/// ```
+fn front_matter_community(tweet: &Tweet, args: &Args) -> String {
+	let Some(community_id) = &tweet.community_id_str else {
+		return String::new();
+	};
+
+	let mut lines: Vec<String> = vec![];
+	lines.push("  community:".into());
+	lines.push(format!("    id: {:?}", community_id));
+
+	if let Some(name) = args.community_name.iter().find_map(|entry| {
+		let (id, name) = entry.split_once('=')?;
+		(id.trim() == community_id).then(|| name.trim())
+	}) {
+		lines.push(format!("    name: {:?}", name));
+	}
+
+	lines.push(format!(
+		"    url: {}",
+		twitter_url_community(community_id, args)
+	));
+
+	lines.join("\n")
+}
+
+/// List where Tweet was exported from into YAML compatible syntax, when `--provenance` is set
+///
+/// ## Example output YAML
+///
+/// ```yaml
+///   provenance:
+///     archive_generation_date: "2023-08-31 12:31:07 +0000"
+///     source: data/tweets.js
+///     input_paths: ["twitter-2023-08-31-abc.zip"]
+///     account_id: "111111111"
+/// ```
+///
+/// Tweets of merged exports list generation date and `--input-path` values of the newest export
+/// that held them, instead of the newest export of all.
+fn front_matter_provenance(tweet: &Tweet, context: &Context, args: &Args) -> String {
+	if !args.provenance {
+		return String::new();
+	}
+
+	let mut lines: Vec<String> = vec![];
+	lines.push("  provenance:".into());
+
+	let generation_date = tweet.source_generation_date.or(context
+		.archive_info
+		.as_ref()
+		.map(|archive_info| archive_info.generation_date));
+	if let Some(generation_date) = generation_date {
+		lines.push(format!(
+			"    archive_generation_date: {:?}",
+			generation_date.format(&args.post_date_format).to_string()
+		));
+	}
+
+	if let Some(source_file) = &tweet.source_file {
+		lines.push(format!("    source: {}", source_file));
+	}
+
+	if !tweet.source_input_paths.is_empty() {
+		lines.push(format!("    input_paths: {:?}", tweet.source_input_paths));
+	}
+
+	if let Some(user_info) = &context.user_info {
+		lines.push(format!("    account_id: {:?}", user_info.account_id));
+	}
+
+	match lines.len() {
+		1 => String::new(),
+		_ => lines.join("\n"),
+	}
+}
+
+/// List original author of old-style retweet, retweeted Tweet when its ID is known, and link of
+/// retweet itself, into YAML compatible syntax
+fn front_matter_retweet_of(tweet: &Tweet, args: &Args) -> Vec<String> {
+	let Some((screen_name, _)) = tweet.retweet_of() else {
+		return vec![];
+	};
+
+	let mut lines: Vec<String> = vec![];
+	lines.push("  retweet_of:".into());
+	lines.push(format!(
+		"    account: {}",
+		twitter_url_account(screen_name, args)
+	));
+	if let Some(retweeted_id_str) = tweet.retweeted_id_str() {
+		lines.push(format!(
+			"    post: {}",
+			twitter_url_status(&retweeted_id_str, args)
+		));
+	}
+	lines.push(format!(
+		"    retweet_url: {}",
+		twitter_url_status(&tweet.id_str, args)
+	));
+
+	lines
+}
+
 /// Convert Tweeted hashtags into YAML compatible list of strings
 fn front_matter_tags(tweet: &Tweet, _args: &Args) -> String {
 	tweet
@@ -300,7 +1315,10 @@ fn front_matter_tags(tweet: &Tweet, _args: &Args) -> String {
 }
 
 /// Extract and/or re-format various links from Tweet into YAML compatible syntax
-fn front_matter_links(tweet: &Tweet, args: &Args) -> String {
+///
+/// Replies to, and links of, Tweets found within archive point to local posts instead of
+/// `twitter.com`
+fn front_matter_links(tweet: &Tweet, context: &Context, args: &Args) -> String {
 	let mut lines: Vec<String> = vec![];
 
 	lines.push(format!("{}:", args.post_twitter_key));
@@ -324,7 +1342,7 @@ fn front_matter_links(tweet: &Tweet, args: &Args) -> String {
 
 		lines.push(format!(
 			"    post: {}",
-			twitter_url_status(&in_reply_to_status_id_str, args)
+			link_status(&in_reply_to_status_id_str, context, args)
 		));
 
 		let user_mentions = tweet
@@ -342,11 +1360,17 @@ fn front_matter_links(tweet: &Tweet, args: &Args) -> String {
 		lines.push(front_matter_mentions(&tweet.entities.user_mentions, args));
 	}
 
+	lines.extend(front_matter_retweet_of(tweet, args));
+
+	if let Some(quote_of) = QuoteOf::new(tweet) {
+		lines.push(format!("  quote_of: {:?}", quote_of.id_str));
+	}
+
 	let urls = tweet
 		.entities
 		.urls
 		.iter()
-		.map(|url| url.expanded_url.clone())
+		.map(|url| link_url(&url.expanded_url, context, args))
 		.collect::<Vec<String>>();
 
 	if !urls.is_empty() {
@@ -359,6 +1383,128 @@ fn front_matter_links(tweet: &Tweet, args: &Args) -> String {
 	lines.join("\n")
 }
 
+/// List URL paths of media files copied from archive into YAML compatible syntax
+fn front_matter_media(tweet: &Tweet, context: &Context, args: &Args) -> String {
+	let urls = tweet
+		.media()
+		.iter()
+		.filter_map(|media| context.media_url(&tweet.id_str, media, args))
+		.collect::<Vec<String>>();
+
+	if urls.is_empty() {
+		return String::new();
+	}
+
+	let mut lines: Vec<String> = vec![];
+	lines.push("  media:".into());
+	urls.iter().for_each(|url| {
+		lines.push(format!("    - {}", url));
+	});
+
+	lines.join("\n")
+}
+
+/// List ID, date, and link of earlier versions of edited Tweet into YAML compatible syntax
+///
+/// ## Example output YAML
+///
+/// ```yaml
+///   edits:
+///     - id: "1690395372546301952"
+///       date: "2023-08-12 16:10:37 +0000"
+///       post: https://twitter.com/i/web/status/1690395372546301952
+/// ```
+fn front_matter_edits(tweet: &Tweet, context: &Context, args: &Args) -> String {
+	let Some(versions) = context.edits.get(&tweet.id_str) else {
+		return String::new();
+	};
+
+	let mut lines: Vec<String> = vec![];
+
+	lines.push("  edits:".into());
+	for version in versions {
+		lines.push(format!("    - id: {:?}", version.id_str));
+		lines.push(format!(
+			"      date: {:?}",
+			version
+				.created_at
+				.format(&args.post_date_format)
+				.to_string()
+		));
+		lines.push(format!(
+			"      post: {}",
+			twitter_url_status(&version.id_str, args)
+		));
+	}
+
+	lines.join("\n")
+}
+
+/// List ID and link of every Tweet in thread into YAML compatible syntax, expected to follow
+/// other lines under `--post-twitter-key`
+fn front_matter_thread(tweets: &[&Tweet], args: &Args) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push("  thread:".into());
+	for tweet in tweets {
+		lines.push(format!("    - id: {:?}", tweet.id_str));
+		lines.push(format!(
+			"      post: {}",
+			twitter_url_status(&tweet.id_str, args)
+		));
+	}
+
+	lines.join("\n")
+}
+
+/// List details of `video` and `animated_gif` media into YAML compatible syntax
+///
+/// ## Example output YAML
+///
+/// ```yaml
+///   videos:
+///     - type: video
+///       src: ../assets/tweets/1697011324369178968-AbCd.mp4
+///       poster: https://pbs.twimg.com/ext_tw_video_thumb/1697011310355959808/pu/img/IjKl.jpg
+///       duration_millis: 30033
+///       aspect_ratio: "16:9"
+/// ```
+fn front_matter_videos(tweet: &Tweet, context: &Context, args: &Args) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	for media in tweet.media().iter().filter(|media| media.is_video()) {
+		let Some(video_info) = &media.video_info else {
+			continue;
+		};
+
+		lines.push(format!("    - type: {}", media.media_type));
+
+		if let Some(src) = context.media_src(&tweet.id_str, media, args) {
+			lines.push(format!("      src: {}", src));
+		}
+
+		lines.push(format!(
+			"      poster: {}",
+			context.media_poster(&tweet.id_str, media, args)
+		));
+
+		if let Some(duration_millis) = &video_info.duration_millis {
+			lines.push(format!("      duration_millis: {}", duration_millis));
+		}
+
+		if let [width, height] = video_info.aspect_ratio.as_slice() {
+			lines.push(format!("      aspect_ratio: \"{}:{}\"", width, height));
+		}
+	}
+
+	if lines.is_empty() {
+		return String::new();
+	}
+
+	lines.insert(0, "  videos:".into());
+	lines.join("\n")
+}
+
 /// Assumes caller has already checked if `user_mentions.len()` is greater than `0`
 fn front_matter_mentions(user_mentions: &Vec<TweetEntitiesUserMention>, args: &Args) -> String {
 	let mut lines: Vec<String> = vec![];
@@ -374,12 +1520,111 @@ fn front_matter_mentions(user_mentions: &Vec<TweetEntitiesUserMention>, args: &A
 	lines.join("\n")
 }
 
+/// Prefix every line of `text` with MarkDown blockquote marker
+pub fn markdown_blockquote(text: &str) -> String {
+	text.trim_end()
+		.lines()
+		.map(|line| match line.is_empty() {
+			true => ">".to_string(),
+			false => format!("> {}", line),
+		})
+		.collect::<Vec<String>>()
+		.join("\n")
+}
+
+/// Escape characters that would break out of double-quoted HTML attribute value
+fn html_escape_attribute(value: &str) -> String {
+	value
+		.replace('&', "&amp;")
+		.replace('"', "&quot;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+}
+
+/// Prevent image alternate text from closing MarkDown link label early
+fn markdown_escape_alt(alt: &str) -> String {
+	alt.replace('\\', "\\\\")
+		.replace('[', "\\[")
+		.replace(']', "\\]")
+		.replace('\n', " ")
+}
+
+/// Link to local post for archived Tweet with `id_str`, otherwise full URL path
+fn link_status(id_str: &str, context: &Context, args: &Args) -> String {
+	context
+		.local_url(id_str, args)
+		.unwrap_or_else(|| twitter_url_status(id_str, args))
+}
+
+/// Replace `url` that points at an archived Tweet with link to local post, other URLs are
+/// returned unchanged
+fn link_url(url: &str, context: &Context, args: &Args) -> String {
+	twitter_status_id(url)
+		.and_then(|id_str| context.local_url(id_str, args))
+		.unwrap_or_else(|| url.to_string())
+}
+
+/// Parse Tweet ID from URLs similar to;
+///
+/// - `https://twitter.com/S0AndS0/status/1697011324369178968`
+/// - `https://mobile.twitter.com/S0AndS0/status/1697011324369178968?s=20`
+/// - `https://x.com/i/web/status/1697011324369178968`
+fn twitter_status_id(url: &str) -> Option<&str> {
+	let rest = url
+		.strip_prefix("https://")
+		.or_else(|| url.strip_prefix("http://"))?;
+
+	let (host, path) = rest.split_once('/')?;
+	let host = host.strip_prefix("www.").unwrap_or(host);
+	let host = host.strip_prefix("mobile.").unwrap_or(host);
+	if host != "twitter.com" && host != "x.com" {
+		return None;
+	}
+
+	let path = path.split(['?', '#']).next()?;
+	let parts = path.split('/').collect::<Vec<&str>>();
+	match parts.as_slice() {
+		[_, "status" | "statuses", id, ..] | ["i", "web", "status", id, ..]
+			if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) =>
+		{
+			Some(id)
+		}
+		_ => None,
+	}
+}
+
 /// Prepend `.id_str` JSON value with full URL path
 fn twitter_url_status(id_str: &str, _args: &Args) -> String {
 	format!("https://twitter.com/i/web/status/{}", id_str)
 }
 
 /// Prepend `.screen_name` JSON value with full URL path
-fn twitter_url_account(screen_name: &str, _args: &Args) -> String {
+pub fn twitter_url_account(screen_name: &str, _args: &Args) -> String {
 	format!("https://twitter.com/{}", screen_name)
 }
+
+/// Prepend account ID, eg. of Direct Message participants without known screen name, with full
+/// URL path
+pub fn twitter_url_user_id(user_id: &str, _args: &Args) -> String {
+	format!("https://twitter.com/i/user/{}", user_id)
+}
+
+/// Prepend `.moment.momentId` JSON value with full URL path
+fn twitter_url_moment(moment_id: &str, _args: &Args) -> String {
+	format!("https://twitter.com/i/moments/{}", moment_id)
+}
+
+/// Prepend `.hashtags[].text` JSON value with full URL path
+fn twitter_url_hashtag(text: &str, _args: &Args) -> String {
+	format!("https://twitter.com/hashtag/{}", text)
+}
+
+/// Prepend `.symbols[].text` JSON value with full URL path for cashtag search
+fn twitter_url_symbol(text: &str, _args: &Args) -> String {
+	format!("https://twitter.com/search?q=%24{}", text)
+}
+
+/// Prepend `.community_id_str` JSON value with full URL path
+fn twitter_url_community(community_id: &str, _args: &Args) -> String {
+	format!("https://twitter.com/i/communities/{}", community_id)
+}
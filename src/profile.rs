@@ -0,0 +1,249 @@
+#!/usr/bin/env rust
+
+//! Write account profile, from archive `data/profile.js`, `data/account.js`, and
+//! `data/screen-name-change.js`, as a single MarkDown page
+//!
+//! Handle history is also recorded within `Context`, so mentions of former handles link to the
+//! site instead of Twitter.
+
+use std::{io, path};
+use twitter_archive::structs::account::{Account, AccountObject};
+use twitter_archive::structs::manifest::Manifest;
+use twitter_archive::structs::screen_name_change::{ScreenNameChange, ScreenNameChangeObject};
+
+use crate::archive::Archive;
+use crate::arguments::Args;
+use crate::media::{self, MediaIndex};
+use crate::output;
+use crate::post_build;
+use crate::structs::{Profile, ProfileObject};
+
+/// Every handle listed by archive `data/screen-name-change.js`, oldest first
+pub fn screen_names(
+	archive: &Archive,
+	data_manifest: &Manifest,
+	args: &Args,
+) -> io::Result<Vec<String>> {
+	let mut result: Vec<String> = vec![];
+
+	for change in screen_name_changes(archive, data_manifest, args)? {
+		for screen_name in [change.changed_from, change.changed_to] {
+			if !result.contains(&screen_name) {
+				result.push(screen_name);
+			}
+		}
+	}
+
+	if args.verbose {
+		eprintln!("profile::screen_names -> {result:?}");
+	}
+
+	Ok(result)
+}
+
+/// Write profile page to `--profile-page`, if defined, copying avatar and header images into
+/// `--media-directory`
+pub fn to_markdown(archive: &Archive, data_manifest: &Manifest, args: &Args) -> io::Result<()> {
+	let Some(profile_page) = &args.profile_page else {
+		return Ok(());
+	};
+
+	let data_types = &data_manifest.data_types;
+	let data_profile: Vec<ProfileObject> = archive.read_data(&data_types.profile.files, args)?;
+	let data_account: Vec<AccountObject> = archive.read_data(&data_types.account.files, args)?;
+	let changes = screen_name_changes(archive, data_manifest, args)?;
+
+	let Some(profile) = data_profile.first().map(|object| &object.profile) else {
+		eprintln!("profile::to_markdown -> No profile found within archive");
+		return Ok(());
+	};
+
+	let user_info = &data_manifest.user_info;
+	let account = data_account.first().map(|object| &object.account);
+	let account_id = account.map_or(&user_info.account_id, |account| &account.account_id);
+	let screen_name = account.map_or(&user_info.user_name, |account| &account.username);
+	let display_name = account.map_or(&user_info.display_name, |account| {
+		&account.account_display_name
+	});
+
+	let profile_page_path = path::Path::new(profile_page);
+	let profile_directory_path = profile_page_path
+		.parent()
+		.unwrap_or(path::Path::new(""))
+		.to_path_buf();
+	output::create_directory(&profile_directory_path, args)?;
+
+	let media_index = match &args.media_directory {
+		Some(_) => MediaIndex::new(archive, &data_types.profile.media_directory, args),
+		None => MediaIndex::default(),
+	};
+	let image_url = |url: &str| -> io::Result<String> {
+		match media_index.find_remote(account_id, url) {
+			Some(file_name) if args.media_directory.is_some() => {
+				media::copy(archive, file_name, args)?;
+				Ok(media::url(file_name, &profile_directory_path, args))
+			}
+			_ => Ok(url.to_string()),
+		}
+	};
+
+	let avatar = Some(&profile.avatar_media_url)
+		.filter(|url| !url.is_empty())
+		.map(|url| image_url(url))
+		.transpose()?;
+	let header = profile
+		.header_media_url
+		.as_ref()
+		.filter(|url| !url.is_empty())
+		.map(|url| image_url(url))
+		.transpose()?;
+
+	let mut lines: Vec<String> = vec![];
+	lines.push(format!("layout: {}", args.profile_layout));
+	lines.push(format!(
+		"title: {:?}",
+		format!("{display_name} (@{screen_name})")
+	));
+	if let Some(account) = account {
+		lines.push(format!(
+			"date: {:?}",
+			account
+				.created_at
+				.format(&args.post_date_format)
+				.to_string()
+		));
+	}
+	if let Some(author) = &args.post_author {
+		lines.push(format!("author: {}", author));
+	}
+	lines.push(format!("{}:", args.post_twitter_key));
+	lines.push(format!(
+		"  account: {}",
+		post_build::twitter_url_account(screen_name, args)
+	));
+	lines.push(format!("  account_id: {:?}", account_id));
+	if let Some(avatar) = &avatar {
+		lines.push(format!("  avatar: {}", avatar));
+	}
+	if let Some(header) = &header {
+		lines.push(format!("  header: {}", header));
+	}
+	if !profile.description.website.is_empty() {
+		lines.push(format!("  website: {}", profile.description.website));
+	}
+	if !profile.description.location.is_empty() {
+		lines.push(format!("  location: {:?}", profile.description.location));
+	}
+	if !changes.is_empty() {
+		lines.push("  screen_names:".into());
+		for change in &changes {
+			lines.push(format!("    - name: {}", change.changed_from));
+			lines.push(format!(
+				"      changed_at: {:?}",
+				change.changed_at.format(&args.post_date_format).to_string()
+			));
+		}
+	}
+
+	let content = format!(
+		"---\n{}\n---\n\n{}\n",
+		lines.join("\n"),
+		content(profile, screen_name, &avatar, &header, account, &changes)
+	);
+
+	output::write_markdown(profile_page_path, &content, args)
+}
+
+/// Entries of archive `data/screen-name-change.js`, oldest first
+fn screen_name_changes(
+	archive: &Archive,
+	data_manifest: &Manifest,
+	args: &Args,
+) -> io::Result<Vec<ScreenNameChange>> {
+	let data_changes: Vec<ScreenNameChangeObject> =
+		archive.read_data(&data_manifest.data_types.screen_name_change.files, args)?;
+
+	let mut changes = data_changes
+		.into_iter()
+		.map(|object| object.screen_name_change.screen_name_change)
+		.collect::<Vec<ScreenNameChange>>();
+	changes.sort_by_key(|change| change.changed_at);
+
+	Ok(changes)
+}
+
+/// Build MarkDown body of profile page
+///
+/// ## Example output MarkDown
+///
+/// ```markdown
+/// ![Header of @S0_And_S0](../assets/images/111111111-1426133346.jpg)
+///
+/// ![Avatar of @S0_And_S0](../assets/images/111111111-HSLiX96Z.jpeg)
+///
+/// Howdy!
+///
+/// - Location: Earth
+/// - Website: [https://t.co/6VtgySlriu](https://t.co/6VtgySlriu)
+/// - Joined: March 2015
+///
+/// ## Former handles
+///
+/// - @S0AndS0, until 2020-01-01
+/// ```
+fn content(
+	profile: &Profile,
+	screen_name: &str,
+	avatar: &Option<String>,
+	header: &Option<String>,
+	account: Option<&Account>,
+	changes: &[ScreenNameChange],
+) -> String {
+	let mut blocks: Vec<String> = vec![];
+
+	if let Some(header) = header {
+		blocks.push(format!("![Header of @{screen_name}]({header})"));
+	}
+	if let Some(avatar) = avatar {
+		blocks.push(format!("![Avatar of @{screen_name}]({avatar})"));
+	}
+	if !profile.description.bio.trim().is_empty() {
+		blocks.push(profile.description.bio.trim().to_string());
+	}
+
+	let mut details: Vec<String> = vec![];
+	if !profile.description.location.is_empty() {
+		details.push(format!("- Location: {}", profile.description.location));
+	}
+	if !profile.description.website.is_empty() {
+		details.push(format!(
+			"- Website: [{0}]({0})",
+			profile.description.website
+		));
+	}
+	if let Some(account) = account {
+		details.push(format!("- Joined: {}", account.created_at.format("%B %Y")));
+	}
+	if !details.is_empty() {
+		blocks.push(details.join("\n"));
+	}
+
+	if !changes.is_empty() {
+		blocks.push("## Former handles".into());
+		blocks.push(
+			changes
+				.iter()
+				.map(|change| {
+					format!(
+						"- @{}, until {}",
+						change.changed_from,
+						change.changed_at.format("%F")
+					)
+				})
+				.collect::<Vec<String>>()
+				.join("\n"),
+		);
+	}
+
+	blocks.join("\n\n")
+}
@@ -0,0 +1,425 @@
+#!/usr/bin/env rust
+
+//! Detect drift between Tweets within archive and structs used to parse them
+//!
+//! Each Tweet is parsed both as untyped JSON and as `TweetObject`, the latter is then serialized
+//! again so that both trees may be walked side by side.  Fields only found within archive are
+//! silently ignored by conversion, while fields only found after parsing were expected but
+//! filled with defaults.  Tweets that fail to parse are compared with JSON types of those that
+//! did parse instead.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::{io, path};
+
+use crate::archive::Archive;
+use crate::arguments::{Args, ReportFormat};
+use crate::structs::{self, TweetObject};
+
+/// Most example Tweet IDs listed per finding
+const EXAMPLES: usize = 3;
+
+/// Whole report of Tweets within archive
+#[derive(Serialize, Debug, Default)]
+struct SchemaCheck {
+	/// Number of Tweets checked
+	tweets: usize,
+
+	/// Errors of Tweets that could not be parsed, keyed by error message
+	parse_errors: Vec<Finding>,
+
+	/// Fields found within archive that structs do not know, keyed by path
+	unknown_fields: Vec<Finding>,
+
+	/// Fields structs expect that archive lacks, keyed by path
+	missing_fields: Vec<Finding>,
+
+	/// Fields whose JSON type differs between archive and structs, keyed by path
+	type_mismatches: Vec<Finding>,
+}
+
+/// Single kind of drift with how often, and where, it happened
+#[derive(Serialize, Debug, Default, Clone)]
+struct Finding {
+	/// Path of field, eg. `tweet.entities.hashtags[].text`, or error message
+	key: String,
+
+	/// JSON type found within archive, only defined for type mismatches
+	#[serde(skip_serializing_if = "Option::is_none")]
+	found: Option<String>,
+
+	/// JSON type structs produce, only defined for type mismatches
+	#[serde(skip_serializing_if = "Option::is_none")]
+	expected: Option<String>,
+
+	/// Number of Tweets finding applies to
+	count: usize,
+
+	/// IDs of first few Tweets finding applies to
+	examples: Vec<String>,
+}
+
+/// Findings of single Tweet, each finding is only counted once per Tweet
+#[derive(Default)]
+struct TweetFindings {
+	/// Paths only found within archive
+	unknown: HashSet<String>,
+
+	/// Paths only found after parsing
+	missing: HashSet<String>,
+
+	/// Paths, with archive and struct types, where types differ
+	mismatched: HashSet<(String, String, String)>,
+}
+
+/// Print report of Tweets within archive at `input_paths` to standard out, returns whether every
+/// Tweet parsed without missing fields or type mismatches
+pub fn print(
+	input_paths: &[path::PathBuf],
+	report_format: ReportFormat,
+	args: &Args,
+) -> io::Result<bool> {
+	let archive = Archive::open(input_paths)?;
+	let data_manifest = archive.manifest()?;
+
+	let data_types = &data_manifest.data_types;
+	let mut files = data_types.tweets.files.clone();
+	files.extend(data_types.community_tweet.files.iter().cloned());
+	files.extend(data_types.deleted_tweets.files.iter().cloned());
+
+	let mut tweets: Vec<serde_json::Value> = vec![];
+	for file in files {
+		match archive.read_data::<serde_json::Value>(std::slice::from_ref(&file), args) {
+			Ok(data) => tweets.extend(data),
+			Err(error) => eprintln!("schema::print -> Skipping {} -> {error}", file.file_name),
+		}
+	}
+
+	let schema_check = check(&tweets);
+	let healthy = schema_check.parse_errors.is_empty()
+		&& schema_check.missing_fields.is_empty()
+		&& schema_check.type_mismatches.is_empty();
+
+	match report_format {
+		ReportFormat::Text => print!("{}", text(&schema_check)),
+		ReportFormat::Json => println!(
+			"{}",
+			serde_json::to_string_pretty(&schema_check)
+				.expect("Unable to serialize schema check as JSON")
+		),
+	}
+
+	Ok(healthy)
+}
+
+/// Compare each untyped Tweet with result of parsing, and serializing, it as `TweetObject`
+///
+/// Tweets that fail to parse are instead compared with JSON types of every Tweet that parsed,
+/// so offending fields can still be named
+fn check(tweets: &[serde_json::Value]) -> SchemaCheck {
+	let mut parse_errors: BTreeMap<String, Finding> = BTreeMap::new();
+	let mut unknown: BTreeMap<String, Finding> = BTreeMap::new();
+	let mut missing: BTreeMap<String, Finding> = BTreeMap::new();
+	let mut mismatched: BTreeMap<(String, String, String), Finding> = BTreeMap::new();
+
+	let parsed = tweets
+		.iter()
+		.map(|raw| {
+			let id = raw
+				.pointer("/tweet/id_str")
+				.or_else(|| raw.pointer("/tweet/id"))
+				.and_then(serde_json::Value::as_str)
+				.unwrap_or("unknown")
+				.to_string();
+
+			// Fields that parsing derives from others are not reported as missing
+			let mut normalized = raw.clone();
+			if let Some(tweet) = normalized.get_mut("tweet") {
+				structs::normalize_edit_info(tweet);
+			}
+
+			let typed = serde_json::from_value::<TweetObject>(raw.clone())
+				.map(|object| {
+					serde_json::to_value(&object).expect("Unable to serialize Tweet as JSON")
+				})
+				.map_err(|error| error.to_string());
+
+			(id, normalized, typed)
+		})
+		.collect::<Vec<(String, serde_json::Value, Result<serde_json::Value, String>)>>();
+
+	let mut reference: BTreeMap<String, &str> = BTreeMap::new();
+	for (_, _, typed) in &parsed {
+		if let Ok(typed) = typed {
+			collect_types("", typed, &mut reference);
+		}
+	}
+
+	for (id, normalized, typed) in &parsed {
+		let mut findings = TweetFindings::default();
+
+		match typed {
+			Ok(typed) => compare("", Some(normalized), Some(typed), &mut findings),
+			Err(error) => {
+				let finding = Finding {
+					key: error.clone(),
+					..Finding::default()
+				};
+				record(&mut parse_errors, error.clone(), finding, id);
+
+				let missing_name = error
+					.strip_prefix("missing field `")
+					.and_then(|rest| rest.split_once('`'))
+					.map(|(name, _)| name);
+				compare_reference("", normalized, &reference, missing_name, &mut findings);
+			}
+		}
+
+		for path in findings.unknown {
+			let finding = Finding {
+				key: path.clone(),
+				..Finding::default()
+			};
+			record(&mut unknown, path, finding, id);
+		}
+		for path in findings.missing {
+			let finding = Finding {
+				key: path.clone(),
+				..Finding::default()
+			};
+			record(&mut missing, path, finding, id);
+		}
+		for (path, found, expected) in findings.mismatched {
+			let finding = Finding {
+				key: path.clone(),
+				found: Some(found.clone()),
+				expected: Some(expected.clone()),
+				..Finding::default()
+			};
+			record(&mut mismatched, (path, found, expected), finding, id);
+		}
+	}
+
+	let sorted = |findings: Vec<Finding>| {
+		let mut findings = findings;
+		findings.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+		findings
+	};
+
+	SchemaCheck {
+		tweets: tweets.len(),
+		parse_errors: sorted(parse_errors.into_values().collect()),
+		unknown_fields: sorted(unknown.into_values().collect()),
+		missing_fields: sorted(missing.into_values().collect()),
+		type_mismatches: sorted(mismatched.into_values().collect()),
+	}
+}
+
+/// Count one more Tweet, with `id`, for finding stored under `key`, starting from `template`
+/// when it is the first
+fn record<K: Ord>(findings: &mut BTreeMap<K, Finding>, key: K, template: Finding, id: &str) {
+	let finding = findings.entry(key).or_insert(template);
+
+	finding.count += 1;
+	if finding.examples.len() < EXAMPLES {
+		finding.examples.push(id.to_string());
+	}
+}
+
+/// Walk `raw` archive JSON and `typed` serialized struct side by side, array entries share a
+/// `[]` suffixed path
+fn compare(
+	path: &str,
+	raw: Option<&serde_json::Value>,
+	typed: Option<&serde_json::Value>,
+	findings: &mut TweetFindings,
+) {
+	use serde_json::Value;
+
+	match (raw, typed) {
+		(Some(Value::Object(raw)), Some(Value::Object(typed))) => {
+			let mut keys = raw.keys().collect::<Vec<&String>>();
+			keys.extend(typed.keys().filter(|key| !raw.contains_key(*key)));
+			for key in keys {
+				compare(
+					&child_path(path, key),
+					raw.get(key),
+					typed.get(key),
+					findings,
+				);
+			}
+		}
+		(Some(Value::Array(raw)), Some(Value::Array(typed))) => {
+			let path = format!("{path}[]");
+			for index in 0..raw.len().max(typed.len()) {
+				compare(&path, raw.get(index), typed.get(index), findings);
+			}
+		}
+		(Some(_), None) => {
+			findings.unknown.insert(path.to_string());
+		}
+		(None, Some(Value::Null)) | (None, None) => {}
+		(None, Some(_)) => {
+			findings.missing.insert(path.to_string());
+		}
+		(Some(raw), Some(typed)) => {
+			let (found, expected) = (type_name(raw), type_name(typed));
+			if found != expected {
+				findings
+					.mismatched
+					.insert((path.to_string(), found.into(), expected.into()));
+			}
+		}
+	}
+}
+
+/// Record JSON type of every non-null field of `typed` serialized struct by path
+fn collect_types(
+	path: &str,
+	typed: &serde_json::Value,
+	reference: &mut BTreeMap<String, &'static str>,
+) {
+	if !typed.is_null() && !path.is_empty() {
+		reference
+			.entry(path.to_string())
+			.or_insert(type_name(typed));
+	}
+
+	match typed {
+		serde_json::Value::Object(object) => {
+			for (key, value) in object {
+				collect_types(&child_path(path, key), value, reference);
+			}
+		}
+		serde_json::Value::Array(array) => {
+			let path = format!("{path}[]");
+			array
+				.iter()
+				.for_each(|value| collect_types(&path, value, reference));
+		}
+		_ => {}
+	}
+}
+
+/// Walk `raw` archive JSON of Tweet that failed to parse, reporting fields whose type differs
+/// from `reference`, and fields named `missing_name` that `reference` lists but `raw` lacks
+fn compare_reference(
+	path: &str,
+	raw: &serde_json::Value,
+	reference: &BTreeMap<String, &str>,
+	missing_name: Option<&str>,
+	findings: &mut TweetFindings,
+) {
+	if let Some(expected) = reference.get(path) {
+		let found = type_name(raw);
+		if found != *expected {
+			findings
+				.mismatched
+				.insert((path.to_string(), found.into(), expected.to_string()));
+			return;
+		}
+	}
+
+	match raw {
+		serde_json::Value::Object(object) => {
+			for (key, value) in object {
+				compare_reference(
+					&child_path(path, key),
+					value,
+					reference,
+					missing_name,
+					findings,
+				);
+			}
+
+			if let Some(missing_name) = missing_name {
+				let missing_path = child_path(path, missing_name);
+				if !object.contains_key(missing_name) && reference.contains_key(&missing_path) {
+					findings.missing.insert(missing_path);
+				}
+			}
+		}
+		serde_json::Value::Array(array) => {
+			let path = format!("{path}[]");
+			for value in array {
+				compare_reference(&path, value, reference, missing_name, findings);
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Path of field `key` within object found at `path`
+fn child_path(path: &str, key: &str) -> String {
+	match path.is_empty() {
+		true => key.to_string(),
+		false => format!("{path}.{key}"),
+	}
+}
+
+/// Name of JSON type of `value`
+fn type_name(value: &serde_json::Value) -> &'static str {
+	match value {
+		serde_json::Value::Null => "null",
+		serde_json::Value::Bool(_) => "boolean",
+		serde_json::Value::Number(_) => "number",
+		serde_json::Value::String(_) => "string",
+		serde_json::Value::Array(_) => "array",
+		serde_json::Value::Object(_) => "object",
+	}
+}
+
+/// Sections of findings with occurrence counts and example Tweet IDs
+///
+/// ## Example output
+///
+/// ```text
+/// Tweets checked: 1500
+///
+/// Unknown fields, ignored by conversion: 1
+///   tweet.extended_entities.media[].sizes -- 42 Tweets, eg. 1697011324369178968
+///
+/// Missing fields, expected by conversion: 0
+///
+/// Type mismatches: 0
+///
+/// Parse errors: 0
+/// ```
+fn text(schema_check: &SchemaCheck) -> String {
+	let mut lines: Vec<String> = vec![];
+
+	lines.push(format!("Tweets checked: {}", schema_check.tweets));
+
+	let sections = [
+		(
+			"Unknown fields, ignored by conversion",
+			&schema_check.unknown_fields,
+		),
+		(
+			"Missing fields, expected by conversion",
+			&schema_check.missing_fields,
+		),
+		("Type mismatches", &schema_check.type_mismatches),
+		("Parse errors", &schema_check.parse_errors),
+	];
+
+	for (title, findings) in sections {
+		lines.push(String::new());
+		lines.push(format!("{title}: {}", findings.len()));
+		for finding in findings {
+			let types = match (&finding.found, &finding.expected) {
+				(Some(found), Some(expected)) => format!(" (found {found}, expected {expected})"),
+				_ => String::new(),
+			};
+			lines.push(format!(
+				"  {}{types} -- {} Tweet{}, eg. {}",
+				finding.key,
+				finding.count,
+				if finding.count == 1 { "" } else { "s" },
+				finding.examples.join(", ")
+			));
+		}
+	}
+
+	format!("{}\n", lines.join("\n"))
+}
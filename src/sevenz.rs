@@ -0,0 +1,108 @@
+#!/usr/bin/env rust
+
+//! Read files within 7z archives by name
+//!
+//! Files of a solid 7z block may only be decompressed in order, so every file is decompressed
+//! once into a temporary file, then read in any order by seeking to where each starts, much like
+//! compressed tar archives.  Encrypted archives are not supported.
+
+use sevenz_rust::{Password, SevenZReader};
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::{fs, io, path};
+
+use crate::tar;
+
+/// 7z archive decompressed into a temporary file, with an index of where each file starts
+pub struct SevenZArchive {
+	/// Concatenated content of every file
+	file: fs::File,
+
+	/// Map of file name to offset, and length, of its content
+	entries: HashMap<String, (u64, u64)>,
+
+	/// Temporary file holding decompressed content, removed once archive is dropped
+	temporary_path: path::PathBuf,
+}
+
+impl SevenZArchive {
+	/// Open 7z archive at `archive_path`, decompressing every file it holds
+	pub fn new(archive_path: &path::Path) -> io::Result<Self> {
+		let mut reader = SevenZReader::open(archive_path, Password::empty())
+			.map_err(|error| invalid_data(archive_path, error))?;
+
+		let temporary_path = tar::temporary_path(archive_path);
+		let mut output = fs::OpenOptions::new()
+			.read(true)
+			.write(true)
+			.create_new(true)
+			.open(&temporary_path)?;
+
+		let mut entries: HashMap<String, (u64, u64)> = HashMap::new();
+		let mut offset: u64 = 0;
+		let result = reader.for_each_entries(|entry, content| {
+			if entry.is_directory() {
+				return Ok(true);
+			}
+
+			let size = io::copy(content, &mut output)?;
+			let name = entry.name().replace('\\', "/");
+			let name = name.trim_start_matches("./").to_string();
+			entries.entry(name).or_insert((offset, size));
+			offset += size;
+
+			Ok(true)
+		});
+
+		if let Err(error) = result {
+			let _ = fs::remove_file(&temporary_path);
+			return Err(invalid_data(archive_path, error));
+		}
+
+		Ok(Self {
+			file: output,
+			entries,
+			temporary_path,
+		})
+	}
+
+	/// Every file name held, eg. `data/tweets.js`
+	pub fn file_names(&self) -> impl Iterator<Item = &str> {
+		self.entries.keys().map(String::as_str)
+	}
+
+	/// Length, in bytes, of `file_name`
+	pub fn size(&self, file_name: &str) -> Option<u64> {
+		self.entries.get(file_name).map(|(_, size)| *size)
+	}
+
+	/// Stream content of `file_name`
+	pub fn by_name(&mut self, file_name: &str) -> io::Result<io::Take<&mut fs::File>> {
+		let (offset, size) = *self.entries.get(file_name).ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::NotFound,
+				format!("No such file within 7z archive -> {file_name}"),
+			)
+		})?;
+
+		self.file.seek(SeekFrom::Start(offset))?;
+		Ok((&mut self.file).take(size))
+	}
+}
+
+impl Drop for SevenZArchive {
+	fn drop(&mut self) {
+		let _ = fs::remove_file(&self.temporary_path);
+	}
+}
+
+/// Describe failure to read 7z archive at `archive_path`
+fn invalid_data(archive_path: &path::Path, error: sevenz_rust::Error) -> io::Error {
+	io::Error::new(
+		io::ErrorKind::InvalidData,
+		format!(
+			"Unable to read 7z archive -> {} -> {error}",
+			archive_path.display()
+		),
+	)
+}
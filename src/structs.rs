@@ -0,0 +1,661 @@
+#!/usr/bin/env rust
+
+//! Archive data that `twitter_archive` crate does not (yet) parse
+//!
+//! Each wrapper flattens the upstream structure, so all existing fields remain reachable via
+//! `Deref`, while adding the extra fields this application needs.
+
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::ops::Deref;
+use twitter_archive::convert;
+
+/// Same shape as `twitter_archive::structs::tweets::TweetObject` but wraps extended `Tweet`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TweetObject {
+	/// Tweet data with extended entities
+	pub tweet: Tweet,
+}
+
+/// Upstream `Tweet` plus `extended_entities`
+///
+/// ## Example JSON data
+///
+/// ```json
+/// {
+///   "tweet": {
+///     "id_str": "1697011324369178968",
+///     "full_text": "Look at this https://t.co/AbCdEf1234",
+///     "extended_entities": {
+///       "media": [
+///         {
+///           "expanded_url": "https://twitter.com/S0AndS0/status/1697011324369178968/photo/1",
+///           "indices": ["13", "36"],
+///           "url": "https://t.co/AbCdEf1234",
+///           "media_url_https": "https://pbs.twimg.com/media/F4zXYZaWcAA1b2c.jpg",
+///           "id_str": "1697011310355959808",
+///           "type": "photo",
+///           "display_url": "pic.twitter.com/AbCdEf1234"
+///         }
+///       ]
+///     }
+///   }
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Tweet {
+	/// Fields parsed by `twitter_archive` crate
+	#[serde(flatten, deserialize_with = "deserialize_inner")]
+	pub inner: twitter_archive::structs::tweets::Tweet,
+
+	/// Photos, videos, and animated GIFs attached to Tweet
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub extended_entities: Option<TweetExtendedEntities>,
+
+	/// ID of quoted Tweet, only included by some archives
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub quoted_status_id_str: Option<String>,
+
+	/// ID of X Community Tweet was posted within, only defined for entries of
+	/// `data/community-tweet.js`
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub community_id_str: Option<String>,
+
+	/// Whether Tweet was loaded from `data/community-tweet.js` instead of `data/tweets.js`
+	#[serde(skip)]
+	pub community_tweet: bool,
+
+	/// When Tweet was deleted, only defined for entries of `data/deleted-tweets.js`
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "created_at_option"
+	)]
+	pub deleted_at: Option<DateTime<Utc>>,
+
+	/// Archive file Tweet was loaded from, eg. `data/tweets-part1.js`
+	#[serde(skip)]
+	pub source_file: Option<String>,
+
+	/// When export Tweet was loaded from was generated, only defined when several exports were
+	/// merged, since `Context` then describes the newest of them
+	#[serde(skip)]
+	pub source_generation_date: Option<DateTime<Utc>>,
+
+	/// `--input-path` values of export Tweet was loaded from, only defined when several exports
+	/// were merged
+	#[serde(skip)]
+	pub source_input_paths: Vec<String>,
+
+	/// Rich text styling ranges, only defined once `full_text` was replaced by a note Tweet
+	#[serde(skip)]
+	pub styles: Vec<TweetStyle>,
+}
+
+/// Kinds of rich text styling long-form, note, Tweets may apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TweetStyleKind {
+	/// Rendered as MarkDown `**strong**`
+	Bold,
+
+	/// Rendered as MarkDown `*emphasis*`
+	Italic,
+}
+
+/// Styled range of `full_text`
+#[derive(Debug, Clone)]
+pub struct TweetStyle {
+	/// Applied styling
+	pub kind: TweetStyleKind,
+
+	/// Start and end character positions within `full_text`
+	pub indices: [usize; 2],
+}
+
+impl Deref for Tweet {
+	type Target = twitter_archive::structs::tweets::Tweet;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
+
+/// Parse upstream `Tweet` after `normalize_edit_info`
+fn deserialize_inner<'de, D>(
+	deserializer: D,
+) -> Result<twitter_archive::structs::tweets::Tweet, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let mut value = serde_json::Value::deserialize(deserializer)?;
+	normalize_edit_info(&mut value);
+
+	serde_json::from_value(value).map_err(de::Error::custom)
+}
+
+/// Archives list later versions of edited Tweets with `edit_info.edit.editControlInitial` in
+/// place of `edit_info.initial`, which `twitter_archive` crate requires, so the former is copied
+/// to the latter of untyped Tweet `value`
+pub fn normalize_edit_info(value: &mut serde_json::Value) {
+	if let Some(edit_info) = value
+		.get_mut("edit_info")
+		.and_then(serde_json::Value::as_object_mut)
+	{
+		if !edit_info.contains_key("initial") {
+			let edit_control_initial = edit_info
+				.get("edit")
+				.and_then(|edit| edit.get("editControlInitial"))
+				.cloned();
+
+			if let Some(edit_control_initial) = edit_control_initial {
+				edit_info.insert("initial".into(), edit_control_initial);
+			}
+		}
+	}
+}
+
+/// Same as `twitter_archive::convert::created_at` for optional fields, eg. `deleted_at`
+mod created_at_option {
+	use chrono::{DateTime, Utc};
+	use serde::{Deserializer, Serializer};
+	use twitter_archive::convert::created_at;
+
+	pub fn serialize<S>(date: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		match date {
+			Some(date) => created_at::serialize(date, serializer),
+			None => serializer.serialize_none(),
+		}
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		created_at::deserialize(deserializer).map(Some)
+	}
+}
+
+impl Tweet {
+	/// IDs of every version of Tweet, oldest first, as listed by `edit_info`
+	pub fn edit_tweet_ids(&self) -> &[String] {
+		&self.edit_info.initial.edit_tweet_ids
+	}
+
+	/// Screen name of original author, and byte length of `RT @screen_name: ` prefix, when
+	/// Tweet is an old-style retweet
+	pub fn retweet_of(&self) -> Option<(&str, usize)> {
+		let rest = self.full_text.strip_prefix("RT @")?;
+		let (screen_name, after) = rest.split_once(':')?;
+
+		if screen_name.is_empty()
+			|| !screen_name
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || c == '_')
+		{
+			return None;
+		}
+
+		let whitespace = after.len() - after.trim_start().len();
+		let prefix_len = "RT @".len() + screen_name.len() + ":".len() + whitespace;
+
+		Some((screen_name, prefix_len))
+	}
+
+	/// ID of Tweet that old-style retweet copied, when its media or links point at that Tweet, eg.
+	/// `https://twitter.com/<screen_name>/status/<id>/photo/1`
+	pub fn retweeted_id_str(&self) -> Option<String> {
+		let (screen_name, _) = self.retweet_of()?;
+		let prefix = format!("/{}/status/", screen_name.to_lowercase());
+
+		self.media()
+			.iter()
+			.map(|media| &media.expanded_url)
+			.chain(self.entities.urls.iter().map(|url| &url.expanded_url))
+			.find_map(|expanded_url| {
+				let expanded_url = expanded_url.to_lowercase();
+				let (_, rest) = expanded_url.split_once(&prefix)?;
+				let id_str = rest
+					.chars()
+					.take_while(char::is_ascii_digit)
+					.collect::<String>();
+				match id_str.is_empty() || id_str == self.id_str {
+					true => None,
+					false => Some(id_str),
+				}
+			})
+	}
+
+	/// List of media attached to Tweet, or empty slice if there are none
+	pub fn media(&self) -> &[TweetMedia] {
+		match &self.extended_entities {
+			Some(extended_entities) => &extended_entities.media,
+			None => &[],
+		}
+	}
+}
+
+/// Container for `.tweets[].tweet.extended_entities.media[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TweetExtendedEntities {
+	/// All media attached to Tweet, unlike `entities.media` which only lists first
+	#[serde(default)]
+	pub media: Vec<TweetMedia>,
+}
+
+/// Single entry of `.tweets[].tweet.extended_entities.media[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TweetMedia {
+	/// Shortened `https://t.co/...` URL found within `full_text`
+	pub url: String,
+
+	/// Link to media view on `twitter.com`
+	pub expanded_url: String,
+
+	/// Shortened URL intended to be displayed to readers, eg. `pic.twitter.com/...`
+	pub display_url: String,
+
+	/// Start and end character positions of `url` within `full_text`
+	#[serde(with = "convert::indices")]
+	pub indices: [usize; 2],
+
+	/// Unique media ID
+	pub id_str: String,
+
+	/// Remote URL of image, or poster frame for videos
+	pub media_url_https: String,
+
+	/// One of `photo`, `video`, or `animated_gif`
+	#[serde(rename = "type")]
+	pub media_type: String,
+
+	/// Alternate text describing media, only included by some archives
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub ext_alt_text: Option<String>,
+
+	/// Encodings available for `video` and `animated_gif` media types
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub video_info: Option<TweetMediaVideoInfo>,
+}
+
+impl TweetMedia {
+	/// Returns `true` for `video` and `animated_gif` media types
+	pub fn is_video(&self) -> bool {
+		self.media_type == "video" || self.media_type == "animated_gif"
+	}
+
+	/// Returns `true` for `animated_gif` media type, which should loop without sound
+	pub fn is_animated_gif(&self) -> bool {
+		self.media_type == "animated_gif"
+	}
+
+	/// MP4 variant with highest bitrate, if any
+	pub fn best_variant(&self) -> Option<&TweetMediaVideoVariant> {
+		self.video_info
+			.as_ref()?
+			.variants
+			.iter()
+			.filter(|variant| variant.content_type == "video/mp4")
+			.max_by_key(|variant| variant.bitrate())
+	}
+}
+
+/// Data found under `.tweets[].tweet.extended_entities.media[].video_info`
+///
+/// ## Example JSON data
+///
+/// ```json
+/// {
+///   "video_info": {
+///     "aspect_ratio": ["16", "9"],
+///     "duration_millis": "30033",
+///     "variants": [
+///       {
+///         "bitrate": "2176000",
+///         "content_type": "video/mp4",
+///         "url": "https://video.twimg.com/ext_tw_video/1697011310355959808/pu/vid/1280x720/AbCd.mp4?tag=12"
+///       },
+///       {
+///         "content_type": "application/x-mpegURL",
+///         "url": "https://video.twimg.com/ext_tw_video/1697011310355959808/pu/pl/EfGh.m3u8?tag=12"
+///       }
+///     ]
+///   }
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TweetMediaVideoInfo {
+	/// Width and height ratio, eg. `["16", "9"]`
+	#[serde(default)]
+	pub aspect_ratio: Vec<String>,
+
+	/// Length of video in milliseconds, not defined for `animated_gif` media
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub duration_millis: Option<String>,
+
+	/// Available encodings of video
+	#[serde(default)]
+	pub variants: Vec<TweetMediaVideoVariant>,
+}
+
+/// Single entry of `.tweets[].tweet.extended_entities.media[].video_info.variants[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TweetMediaVideoVariant {
+	/// Bits per second, not defined for streaming playlists
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub bitrate: Option<String>,
+
+	/// MIME type, eg. `video/mp4`
+	pub content_type: String,
+
+	/// Remote URL of encoded video
+	pub url: String,
+}
+
+impl TweetMediaVideoVariant {
+	/// Parsed `bitrate`, or `0` when not defined
+	pub fn bitrate(&self) -> usize {
+		self.bitrate
+			.as_ref()
+			.and_then(|bitrate| bitrate.parse::<usize>().ok())
+			.unwrap_or(0)
+	}
+}
+
+/// Single entry of `data/note-tweet.js`, which holds complete text of Tweets longer than 280
+/// characters
+///
+/// ## Example JSON data
+///
+/// ```json
+/// {
+///   "noteTweet": {
+///     "noteTweetId": "1697011310355959809",
+///     "updatedAt": "2023-08-30T22:20:03.000Z",
+///     "createdAt": "2023-08-30T22:20:03.000Z",
+///     "core": {
+///       "styletags": [
+///         {
+///           "styleTypes": [{ "value": "1", "name": "Bold" }],
+///           "fromIndex": "0",
+///           "toIndex": "7"
+///         }
+///       ],
+///       "urls": [],
+///       "text": "Anyone else occasionally annoyed ...",
+///       "mentions": [],
+///       "cashtags": [],
+///       "hashtags": []
+///     }
+///   }
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTweetObject {
+	/// Why they wrapped each note within unnecessary object label is anyone's guess
+	pub note_tweet: NoteTweet,
+}
+
+/// Data found under `.noteTweet` of `data/note-tweet.js` entries
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTweet {
+	/// Unique note ID, usually differs from ID of Tweet it belongs to
+	pub note_tweet_id: String,
+
+	/// ID of Tweet note belongs to, only included by some archives
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tweet_id: Option<String>,
+
+	/// When note, and so Tweet it belongs to, was created
+	#[serde(with = "convert::date_time_iso_8601")]
+	pub created_at: DateTime<Utc>,
+
+	/// Text and entities of note
+	pub core: NoteTweetCore,
+}
+
+/// Data found under `.noteTweet.core` of `data/note-tweet.js` entries
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTweetCore {
+	/// Complete, un-truncated, text of Tweet
+	pub text: String,
+
+	/// Shared links
+	#[serde(default)]
+	pub urls: Vec<NoteTweetUrl>,
+
+	/// Mentioned accounts
+	#[serde(default)]
+	pub mentions: Vec<NoteTweetMention>,
+
+	/// Hashtags without leading `#`
+	#[serde(default)]
+	pub hashtags: Vec<NoteTweetHashtag>,
+
+	/// Symbols without leading `$`
+	#[serde(default)]
+	pub cashtags: Vec<NoteTweetCashtag>,
+
+	/// Rich text styling ranges
+	#[serde(default)]
+	pub styletags: Vec<NoteTweetStyletag>,
+}
+
+/// Single entry of `.noteTweet.core.urls[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTweetUrl {
+	/// Shortened `https://t.co/...` URL found within `text`
+	pub short_url: String,
+
+	/// Original URL
+	pub expanded_url: String,
+
+	/// Shortened URL intended to be displayed to readers
+	pub display_url: String,
+
+	/// Start character position of `short_url` within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub from_index: usize,
+
+	/// End character position of `short_url` within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub to_index: usize,
+}
+
+/// Single entry of `.noteTweet.core.mentions[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTweetMention {
+	/// Mentioned account name without leading `@`
+	pub screen_name: String,
+
+	/// Start character position of mention within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub from_index: usize,
+
+	/// End character position of mention within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub to_index: usize,
+}
+
+/// Single entry of `.noteTweet.core.hashtags[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTweetHashtag {
+	/// Hashtag without leading `#`
+	pub hashtag_value: String,
+
+	/// Start character position of hashtag within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub from_index: usize,
+
+	/// End character position of hashtag within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub to_index: usize,
+}
+
+/// Single entry of `.noteTweet.core.cashtags[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTweetCashtag {
+	/// Symbol without leading `$`
+	pub cashtag_value: String,
+
+	/// Start character position of symbol within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub from_index: usize,
+
+	/// End character position of symbol within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub to_index: usize,
+}
+
+/// Single entry of `.noteTweet.core.styletags[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTweetStyletag {
+	/// Styles applied to range, eg. `Bold` and/or `Italic`
+	#[serde(default)]
+	pub style_types: Vec<NoteTweetStyleType>,
+
+	/// Start character position of styled range within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub from_index: usize,
+
+	/// End character position of styled range within `text`
+	#[serde(with = "convert::number_like_string")]
+	pub to_index: usize,
+}
+
+/// Single entry of `.noteTweet.core.styletags[].styleTypes[]`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NoteTweetStyleType {
+	/// Name of style, eg. `Bold` or `Italic`
+	pub name: String,
+}
+
+/// Same shape as `twitter_archive::structs::profile::ProfileObject` but wraps extended `Profile`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ProfileObject {
+	/// Profile data with header media
+	pub profile: Profile,
+}
+
+/// Upstream `Profile` plus `headerMediaUrl`
+///
+/// ## Example JSON data
+///
+/// ```json
+/// {
+///   "profile": {
+///     "description": {
+///       "bio": "Howdy!",
+///       "website": "https://t.co/6VtgySlriu",
+///       "location": ""
+///     },
+///     "avatarMediaUrl": "https://pbs.twimg.com/profile_images/575070434267279361/HSLiX96Z.jpeg",
+///     "headerMediaUrl": "https://pbs.twimg.com/profile_banners/111111111/1426133346"
+///   }
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+	/// Fields parsed by `twitter_archive` crate
+	#[serde(flatten)]
+	pub inner: twitter_archive::structs::profile::Profile,
+
+	/// Banner image shown above profile, not listed by archives of accounts that never set one
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub header_media_url: Option<String>,
+}
+
+impl Deref for Profile {
+	type Target = twitter_archive::structs::profile::Profile;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
+
+/// Entry of `data/moment.js`, which `twitter_archive` crate does not parse
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MomentObject {
+	/// Moment data
+	pub moment: Moment,
+}
+
+/// Curated, ordered, collection of Tweets
+///
+/// ## Example JSON data
+///
+/// ```json
+/// {
+///   "moment": {
+///     "momentId": "1000000000000000001",
+///     "createdAt": "2018-03-01T12:00:00.000Z",
+///     "createdBy": "111111111",
+///     "title": "Tips worth keeping",
+///     "description": "Collection of favourite technical tips",
+///     "coverMediaUrls": ["https://pbs.twimg.com/media/DXyZaBcDeFg.jpg"],
+///     "tweets": [
+///       { "momentId": "1000000000000000001", "tweet": { "tweetId": "1697011324369178968" } }
+///     ]
+///   }
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Moment {
+	/// ID of Moment, also prefixes names of its media files within archive
+	pub moment_id: String,
+
+	/// When Moment was created
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub created_at: Option<DateTime<Utc>>,
+
+	/// Headline of Moment
+	#[serde(default)]
+	pub title: String,
+
+	/// Summary shown below headline
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+
+	/// Remote URLs of media shown as cover of Moment
+	#[serde(default)]
+	pub cover_media_urls: Vec<String>,
+
+	/// IDs of member Tweets in order they were curated
+	#[serde(default, deserialize_with = "deserialize_moment_tweets")]
+	pub tweets: Vec<String>,
+}
+
+/// Archives have listed member Tweets of Moments as plain IDs as well as objects that nest the
+/// ID under `tweetId`, `id_str`, or `id`, optionally within a `tweet` object, so each shape is
+/// reduced to Tweet ID
+fn deserialize_moment_tweets<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	fn tweet_id(value: &serde_json::Value) -> Option<String> {
+		match value {
+			serde_json::Value::String(id) => Some(id.clone()),
+			serde_json::Value::Number(id) => Some(id.to_string()),
+			serde_json::Value::Object(object) => ["tweetId", "id_str", "id"]
+				.iter()
+				.find_map(|key| object.get(*key).and_then(tweet_id))
+				.or_else(|| object.get("tweet").and_then(tweet_id)),
+			_ => None,
+		}
+	}
+
+	let values = Vec::<serde_json::Value>::deserialize(deserializer)?;
+	Ok(values.iter().filter_map(tweet_id).collect())
+}
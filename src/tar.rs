@@ -0,0 +1,249 @@
+#!/usr/bin/env rust
+
+//! Read files within tar archives, plain or compressed with gzip or zstd, by name
+//!
+//! Only what archives repacked from a Twitter/X export need is understood; regular files, GNU long
+//! names, and PAX `path` records.  Compressed archives are decompressed once into a temporary
+//! file, so files may then be read in any order by seeking to where each starts.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs, io, path, process};
+
+/// Size of tar header, and of blocks that file content is padded to
+const BLOCK: u64 = 512;
+
+/// Number of compressed archives decompressed so far, keeping temporary file names unique even
+/// when archives of same name, from different directories, are opened
+static DECOMPRESSED: AtomicUsize = AtomicUsize::new(0);
+
+/// Compression applied on top of tar archive
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Compression {
+	/// `.tar`
+	None,
+
+	/// `.tar.gz` or `.tgz`
+	Gzip,
+
+	/// `.tar.zst` or `.tzst`
+	Zstd,
+}
+
+/// Tar archive with an index of where each file's content starts
+pub struct TarArchive {
+	/// Uncompressed tar file
+	file: fs::File,
+
+	/// Map of file name to offset, and length, of its content
+	entries: HashMap<String, (u64, u64)>,
+
+	/// Temporary file holding decompressed tar, removed once archive is dropped
+	temporary_path: Option<path::PathBuf>,
+}
+
+impl TarArchive {
+	/// Open tar archive at `tar_path`, decompressing it first when needed
+	pub fn new(tar_path: &path::Path, compression: Compression) -> io::Result<Self> {
+		let (mut file, temporary_path) = match compression {
+			Compression::None => (fs::File::open(tar_path)?, None),
+			Compression::Gzip | Compression::Zstd => {
+				let temporary_path = temporary_path(tar_path);
+
+				let input = fs::File::open(tar_path)?;
+				let mut output = fs::OpenOptions::new()
+					.write(true)
+					.create_new(true)
+					.open(&temporary_path)?;
+				match compression {
+					Compression::Gzip => {
+						io::copy(&mut flate2::read::MultiGzDecoder::new(input), &mut output)?
+					}
+					_ => io::copy(&mut zstd::stream::read::Decoder::new(input)?, &mut output)?,
+				};
+
+				(fs::File::open(&temporary_path)?, Some(temporary_path))
+			}
+		};
+
+		let entries = index(&mut file)?;
+
+		Ok(Self {
+			file,
+			entries,
+			temporary_path,
+		})
+	}
+
+	/// Every file name held, eg. `data/tweets.js`
+	pub fn file_names(&self) -> impl Iterator<Item = &str> {
+		self.entries.keys().map(String::as_str)
+	}
+
+	/// Length, in bytes, of `file_name`
+	pub fn size(&self, file_name: &str) -> Option<u64> {
+		self.entries.get(file_name).map(|(_, size)| *size)
+	}
+
+	/// Stream content of `file_name`
+	pub fn by_name(&mut self, file_name: &str) -> io::Result<io::Take<&mut fs::File>> {
+		let (offset, size) = *self.entries.get(file_name).ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::NotFound,
+				format!("No such file within tar archive -> {file_name}"),
+			)
+		})?;
+
+		self.file.seek(SeekFrom::Start(offset))?;
+		Ok((&mut self.file).take(size))
+	}
+}
+
+impl Drop for TarArchive {
+	fn drop(&mut self) {
+		if let Some(temporary_path) = &self.temporary_path {
+			let _ = fs::remove_file(temporary_path);
+		}
+	}
+}
+
+/// Unique path, within temporary directory, to decompress archive at `archive_path` into
+pub fn temporary_path(archive_path: &path::Path) -> path::PathBuf {
+	env::temp_dir().join(format!(
+		"tweet-archive-to-markdown-{}-{}-{}.tmp",
+		process::id(),
+		DECOMPRESSED.fetch_add(1, Ordering::Relaxed),
+		archive_path
+			.file_name()
+			.map(|name| name.to_string_lossy().to_string())
+			.unwrap_or_default()
+	))
+}
+
+/// Compression that `file_name` suffix implies, or `None` when it names no tar archive
+pub fn compression_of(file_name: &str) -> Option<Compression> {
+	let file_name = file_name.to_lowercase();
+
+	if file_name.ends_with(".tar") {
+		Some(Compression::None)
+	} else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+		Some(Compression::Gzip)
+	} else if file_name.ends_with(".tar.zst") || file_name.ends_with(".tzst") {
+		Some(Compression::Zstd)
+	} else {
+		None
+	}
+}
+
+/// Walk every header of tar `file`, recording where content of each regular file starts
+fn index(file: &mut fs::File) -> io::Result<HashMap<String, (u64, u64)>> {
+	let mut result: HashMap<String, (u64, u64)> = HashMap::new();
+	let mut header = [0u8; BLOCK as usize];
+	let mut offset: u64 = 0;
+	let mut long_name: Option<String> = None;
+
+	file.seek(SeekFrom::Start(0))?;
+	loop {
+		if let Err(error) = file.read_exact(&mut header) {
+			match error.kind() {
+				io::ErrorKind::UnexpectedEof => break,
+				_ => return Err(error),
+			}
+		}
+		offset += BLOCK;
+
+		// Archive ends with blocks of zeros
+		if header.iter().all(|byte| *byte == 0) {
+			break;
+		}
+
+		let size = parse_size(&header[124..136])?;
+		let padded_size = size.div_ceil(BLOCK) * BLOCK;
+
+		match header[156] {
+			// GNU long name of next entry
+			b'L' => {
+				let mut name = vec![0u8; size as usize];
+				file.read_exact(&mut name)?;
+				long_name = Some(c_string(&name));
+				file.seek(SeekFrom::Start(offset + padded_size))?;
+			}
+			// PAX extended header, only `path` of next entry is of interest
+			b'x' => {
+				let mut records = vec![0u8; size as usize];
+				file.read_exact(&mut records)?;
+				if let Some(path) = pax_path(&records) {
+					long_name = Some(path);
+				}
+				file.seek(SeekFrom::Start(offset + padded_size))?;
+			}
+			// Regular files
+			b'0' | b'\0' | b'7' => {
+				let name = long_name.take().unwrap_or_else(|| {
+					let prefix = c_string(&header[345..500]);
+					let name = c_string(&header[0..100]);
+					match prefix.is_empty() {
+						true => name,
+						false => format!("{prefix}/{name}"),
+					}
+				});
+				let name = name.trim_start_matches("./").to_string();
+
+				result.entry(name).or_insert((offset, size));
+				file.seek(SeekFrom::Start(offset + padded_size))?;
+			}
+			// Directories, links, and anything else hold no content worth reading
+			_ => {
+				long_name = None;
+				file.seek(SeekFrom::Start(offset + padded_size))?;
+			}
+		}
+
+		offset += padded_size;
+	}
+
+	Ok(result)
+}
+
+/// Parse octal, or GNU base-256 when high bit of first byte is set, size field of header
+fn parse_size(field: &[u8]) -> io::Result<u64> {
+	if field[0] & 0x80 != 0 {
+		return Ok(field[1..]
+			.iter()
+			.fold(u64::from(field[0] & 0x7f), |size, byte| {
+				(size << 8) | u64::from(*byte)
+			}));
+	}
+
+	let octal = String::from_utf8_lossy(field);
+	let octal = octal.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+	if octal.is_empty() {
+		return Ok(0);
+	}
+
+	u64::from_str_radix(octal, 8).map_err(|error| {
+		io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("Unable to parse tar header size {octal:?} -> {error}"),
+		)
+	})
+}
+
+/// Text of NUL terminated header field
+fn c_string(field: &[u8]) -> String {
+	let end = field
+		.iter()
+		.position(|byte| *byte == 0)
+		.unwrap_or(field.len());
+	String::from_utf8_lossy(&field[..end]).to_string()
+}
+
+/// Value of `path` within PAX records, each formatted as `<length> <key>=<value>\n`
+fn pax_path(records: &[u8]) -> Option<String> {
+	String::from_utf8_lossy(records)
+		.lines()
+		.filter_map(|record| record.split_once(' ').map(|(_, record)| record))
+		.find_map(|record| record.strip_prefix("path="))
+		.map(String::from)
+}
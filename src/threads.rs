@@ -0,0 +1,79 @@
+#!/usr/bin/env rust
+
+//! Reassemble chains of self-replies, aka threads, into ordered groups of Tweets
+//!
+//! Archived `tweets.js` files only contain Tweets of the account that requested the archive, so
+//! any Tweet that replies to another Tweet found within the same archive is a self-reply.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::arguments::Args;
+use crate::structs::Tweet;
+
+/// Group Tweets into threads, each group lists root Tweet first followed by its self-replies
+///
+/// Tweets that neither reply to, nor are replied to by, another archived Tweet are returned as
+/// groups of one.  When a Tweet has more than one self-reply the earliest continues the thread,
+/// and later replies start threads of their own.
+pub fn group<'a>(tweets: &[&'a Tweet], args: &Args) -> Vec<Vec<&'a Tweet>> {
+	let ids = tweets
+		.iter()
+		.map(|tweet| tweet.id_str.as_str())
+		.collect::<HashSet<&str>>();
+
+	let mut children: HashMap<&str, Vec<&Tweet>> = HashMap::new();
+	for tweet in tweets {
+		if let Some(parent_id) = &tweet.in_reply_to_status_id_str {
+			if ids.contains(parent_id.as_str()) && parent_id != &tweet.id_str {
+				children.entry(parent_id.as_str()).or_default().push(tweet);
+			}
+		}
+	}
+
+	children
+		.values_mut()
+		.for_each(|replies| replies.sort_by_key(|tweet| (tweet.created_at, tweet.id_str.clone())));
+
+	let continuations = children
+		.values()
+		.filter_map(|replies| replies.first())
+		.map(|tweet| tweet.id_str.as_str())
+		.collect::<HashSet<&str>>();
+
+	let mut result: Vec<Vec<&Tweet>> = vec![];
+	for tweet in tweets {
+		let mut tweet = *tweet;
+		if continuations.contains(tweet.id_str.as_str()) {
+			continue;
+		}
+
+		let mut thread: Vec<&Tweet> = vec![];
+		let mut seen: HashSet<&str> = HashSet::new();
+		loop {
+			if !seen.insert(tweet.id_str.as_str()) {
+				break;
+			}
+			thread.push(tweet);
+
+			match children
+				.get(tweet.id_str.as_str())
+				.and_then(|replies| replies.first())
+			{
+				Some(next) => tweet = next,
+				None => break,
+			}
+		}
+
+		if args.verbose && thread.len() > 1 {
+			eprintln!(
+				"threads::group -> {} -> Tweets in thread: {}",
+				thread[0].id_str,
+				thread.len()
+			);
+		}
+
+		result.push(thread);
+	}
+
+	result
+}